@@ -4,14 +4,24 @@ import csv
 import json
 import os
 import subprocess
+import sys
 import time
 from collections import defaultdict
 from urllib import request
 
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "ops"))
+import siwe_login  # noqa: E402
 
-def post_json(url, payload):
+# Dev key of the testnet operator started by ops/start_testnet.sh
+DEFAULT_FUNDER_KEY = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+
+def post_json(url, payload, token=None):
     data = json.dumps(payload).encode("utf-8")
-    req = request.Request(url, data=data, headers={"Content-Type": "application/json"})
+    headers = {"Content-Type": "application/json"}
+    if token:
+        headers["Authorization"] = f"Bearer {token}"
+    req = request.Request(url, data=data, headers=headers)
     start = time.time()
     with request.urlopen(req, timeout=10) as resp:
         resp.read()
@@ -46,28 +56,44 @@ def main():
     parser.add_argument("--duration", type=int, default=600)
     parser.add_argument("--rate", type=int, default=50)
     parser.add_argument("--output", default="bench/results/report.json")
+    parser.add_argument(
+        "--funder-key",
+        default=os.environ.get("FUNDER_KEY", DEFAULT_FUNDER_KEY),
+        help="dev key of an account holding native tokens to fund the bench accounts",
+    )
     args = parser.parse_args()
 
-    import os
     os.makedirs(os.path.dirname(args.output), exist_ok=True)
 
     latencies = []
     tx_count = 0
-    start_time = time.time()
-    next_mine = start_time
     mine_interval = 2
 
-    accounts = [f"bench-{i}" for i in range(10)]
+    # Transfers must come from the session's own address, so every bench
+    # account signs in with its own dev key
+    funder = siwe_login.address(args.funder_key)
+    funder_token = siwe_login.sign_in(args.base_url, args.funder_key)
+    keys = [f"0x{0xbe0c0000 + i:064x}" for i in range(10)]
+    accounts = [siwe_login.address(key) for key in keys]
+    tokens = [siwe_login.sign_in(args.base_url, key) for key in keys]
     for account in accounts:
-        post_json(f"{args.base_url}/tokens/transfer", {"from": "admin", "to": account, "amount": 100})
+        post_json(
+            f"{args.base_url}/tokens/transfer",
+            {"from": funder, "to": account, "amount": 100},
+            funder_token,
+        )
+    start_time = time.time()
+    next_mine = start_time
 
     while time.time() - start_time < args.duration:
         for _ in range(args.rate):
-            from_acct = accounts[tx_count % len(accounts)]
+            sender = tx_count % len(accounts)
+            from_acct = accounts[sender]
             to_acct = accounts[(tx_count + 1) % len(accounts)]
             latency = post_json(
                 f"{args.base_url}/tokens/transfer",
                 {"from": from_acct, "to": to_acct, "amount": 1},
+                tokens[sender],
             )
             latencies.append(latency)
             tx_count += 1
@@ -0,0 +1,73 @@
+//! Proof-of-work throughput. Run with `cargo bench --bench pow`; Criterion
+//! reports the `thrpt` line in hashes per second.
+//!
+//! Searches run against a target met by one hash in 65,536 on average, the
+//! work each search is credited with.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::AtomicBool;
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/consensus/pow.rs"]
+mod pow;
+
+/// Four leading zero hex digits.
+const BITS: u32 = 0x1f00_ffff;
+const EXPECTED_HASHES: u64 = 1 << 16;
+
+/// Block header prefixes are a few hundred bytes of formatted fields.
+fn header_prefix(seed: u64) -> Vec<u8> {
+    let mut prefix = format!("{:0>300}", "header").into_bytes();
+    prefix[..8].copy_from_slice(&seed.to_le_bytes());
+    prefix
+}
+
+/// The miner this replaced: formats and hex-encodes every candidate.
+fn string_search(data: &str, difficulty: usize) -> (u64, String) {
+    let mut nonce = 0;
+    loop {
+        let candidate = format!("{}{}", data, nonce);
+        let hash = format!("{:x}", Sha256::digest(candidate.as_bytes()));
+        if hash.starts_with(&"0".repeat(difficulty)) {
+            return (nonce, hash);
+        }
+        nonce += 1;
+    }
+}
+
+fn pow_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pow");
+    group.throughput(Throughput::Elements(EXPECTED_HASHES));
+    group.sample_size(20);
+
+    let mut seed = 0;
+    group.bench_function("string_nonce", |b| {
+        b.iter(|| {
+            seed += 1;
+            let data = String::from_utf8_lossy(&header_prefix(seed)).into_owned();
+            string_search(black_box(&data), 4)
+        })
+    });
+
+    let cancel = AtomicBool::new(false);
+    let mut thread_counts = vec![1, pow::default_threads()];
+    thread_counts.dedup();
+    for threads in thread_counts {
+        group.bench_with_input(
+            BenchmarkId::new("binary_nonce", format!("{}_threads", threads)),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    seed += 1;
+                    let prefix = header_prefix(seed);
+                    pow::mine_block_until(black_box(&prefix), BITS, threads, &cancel)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pow_throughput);
+criterion_main!(benches);
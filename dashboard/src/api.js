@@ -12,6 +12,14 @@ const api = axios.create({
   timeout: 10000, // 10 second timeout
 });
 
+// Attach the wallet session token (set by useWallet) to every request
+api.interceptors.request.use((config) => {
+  if (window.authToken) {
+    config.headers.Authorization = `Bearer ${window.authToken}`;
+  }
+  return config;
+});
+
 // Add response interceptor for error handling
 api.interceptors.response.use(
   (response) => response,
@@ -103,10 +111,6 @@ export const getMiningDifficulty = async () => {
   return api.get("/mining/difficulty");
 };
 
-export const setMiningDifficulty = async (data) => {
-  return api.post("/mining/difficulty", data);
-};
-
 // ===== VALIDATION & SECURITY =====
 
 export const validateBlockchain = async () => {
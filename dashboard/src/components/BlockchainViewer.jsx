@@ -113,19 +113,19 @@ const BlockchainViewer = ({ refreshTrigger }) => {
             <div className="space-y-2 max-h-40 overflow-y-auto">
               {pendingTxs.map((tx, idx) => (
                 <div
-                  key={idx}
+                  key={tx.id}
                   className="bg-gradient-to-r from-orange-500/20 to-yellow-500/20 border border-orange-500/30 rounded-xl p-3 backdrop-blur-sm hover:scale-[1.02] transition-all duration-300"
                 >
                   <div className="flex items-center justify-between mb-2">
                     <span className="text-sm font-medium text-orange-300">
-                      Transaction #{idx + 1}
+                      #{idx + 1} {tx.transaction_type}
                     </span>
                     <span className="text-xs text-orange-400 bg-orange-500/20 px-2 py-1 rounded-lg backdrop-blur-sm animate-pulse">
                       PENDING
                     </span>
                   </div>
                   <code className="text-xs text-orange-200 break-all block bg-white/10 p-3 rounded-lg border border-white/20 backdrop-blur-sm">
-                    {tx}
+                    {tx.from ?? "system"} → {tx.to}: {tx.amount}
                   </code>
                 </div>
               ))}
@@ -149,13 +149,7 @@ const BlockchainViewer = ({ refreshTrigger }) => {
             <div className="space-y-3 max-h-96 overflow-y-auto">
               {blocks.map((block, idx) => {
                 const isGenesis = idx === 0;
-                const blockData =
-                  typeof block === "string" ? block : JSON.stringify(block);
-
-                const indexMatch = blockData.match(/index: (\d+)/);
-                const timestampMatch = blockData.match(/timestamp: ([^,}]+)/);
-                const hashMatch = blockData.match(/hash: "([^"]+)"/);
-                const nonceMatch = blockData.match(/nonce: (\d+)/);
+                const blockData = JSON.stringify(block, null, 2);
 
                 return (
                   <div
@@ -211,7 +205,7 @@ const BlockchainViewer = ({ refreshTrigger }) => {
                             Index:
                           </span>{" "}
                           <span className="text-white">
-                            {indexMatch ? indexMatch[1] : "N/A"}
+                            {block.index ?? "N/A"}
                           </span>
                         </div>
                         <div>
@@ -219,7 +213,7 @@ const BlockchainViewer = ({ refreshTrigger }) => {
                             Nonce:
                           </span>{" "}
                           <span className="text-white">
-                            {nonceMatch ? nonceMatch[1] : "N/A"}
+                            {block.nonce ?? "N/A"}
                           </span>
                         </div>
                         <div>
@@ -227,10 +221,8 @@ const BlockchainViewer = ({ refreshTrigger }) => {
                             Timestamp:
                           </span>{" "}
                           <span className="text-white">
-                            {timestampMatch
-                              ? formatTimestamp(
-                                  timestampMatch[1].replace(/['"]/g, "")
-                                )
+                            {block.timestamp
+                              ? formatTimestamp(block.timestamp)
                               : "N/A"}
                           </span>
                         </div>
@@ -239,7 +231,7 @@ const BlockchainViewer = ({ refreshTrigger }) => {
                             Hash:
                           </span>{" "}
                           <span className="text-white font-mono">
-                            {hashMatch ? truncateHash(hashMatch[1]) : "N/A"}
+                            {block.hash ? truncateHash(block.hash) : "N/A"}
                           </span>
                         </div>
                       </div>
@@ -280,7 +272,9 @@ const BlockchainViewer = ({ refreshTrigger }) => {
                 </div>
                 <div className="bg-white/10 p-4 rounded-xl backdrop-blur-sm border border-white/20">
                   <code className="text-sm text-gray-200 whitespace-pre-wrap break-all">
-                    {selectedBlock.data || "No data available"}
+                    {selectedBlock.data
+                      ? JSON.stringify(selectedBlock.data, null, 2)
+                      : "No data available"}
                   </code>
                 </div>
               </div>
@@ -4,10 +4,9 @@ import { useAuthenticatedApi } from "../hooks/useAuthenticatedApi";
 const API_BASE = "http://127.0.0.1:3000";
 
 const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
-  const [difficulty, setDifficulty] = useState(2);
   const [loading, setLoading] = useState(false);
   const [message, setMessage] = useState("");
-  const [currentDifficulty, setCurrentDifficulty] = useState(2);
+  const [difficulty, setDifficulty] = useState(null);
   const [miningStats, setMiningStats] = useState(null);
 
   const { authenticatedFetch, isConnected } = useAuthenticatedApi();
@@ -20,9 +19,7 @@ const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
     try {
       const response = await fetch(`${API_BASE}/mining/difficulty`);
       if (response.ok) {
-        const result = await response.json();
-        setCurrentDifficulty(result.current_difficulty);
-        setDifficulty(result.current_difficulty);
+        setDifficulty(await response.json());
       }
     } catch (error) {
       console.error("Failed to fetch difficulty:", error);
@@ -69,94 +66,40 @@ const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
     }
   };
 
-  const handleSetDifficulty = async (e) => {
-    e.preventDefault();
-
-    if (!isConnected) {
-      setMessage("Please connect your wallet first");
-      return;
+  // Levels by the average number of hashes a block takes
+  const getDifficultyInfo = (expectedHashes) => {
+    if (!expectedHashes) {
+      return {
+        level: "Unknown",
+        color: "from-gray-500/20 to-slate-500/20",
+        border: "border-gray-500/30",
+      };
     }
-
-    setLoading(true);
-
-    try {
-      const response = await authenticatedFetch("/mining/difficulty", {
-        method: "POST",
-        body: JSON.stringify({ difficulty: parseInt(difficulty) }),
-      });
-
-      const result = await response.json();
-
-      if (response.ok) {
-        setMessage(`${result.message}`);
-        setCurrentDifficulty(parseInt(difficulty));
-      } else {
-        setMessage(
-          `Failed to set difficulty: ${result.message || "Unknown error"}`
-        );
-      }
-    } catch (error) {
-      setMessage(`Error: ${error.message}`);
-    } finally {
-      setLoading(false);
+    if (expectedHashes < 1e4) {
+      return {
+        level: "Easy",
+        color: "from-blue-500/20 to-cyan-500/20",
+        border: "border-blue-500/30",
+      };
     }
-  };
-
-  const getDifficultyInfo = (diff) => {
-    switch (diff) {
-      case 1:
-        return {
-          level: "Very Easy",
-          time: "~1s",
-          color: "from-green-500/20 to-emerald-500/20",
-          border: "border-green-500/30",
-        };
-      case 2:
-        return {
-          level: "Easy",
-          time: "~2-5s",
-          color: "from-blue-500/20 to-cyan-500/20",
-          border: "border-blue-500/30",
-        };
-      case 3:
-        return {
-          level: "Medium",
-          time: "~10-30s",
-          color: "from-yellow-500/20 to-orange-500/20",
-          border: "border-yellow-500/30",
-        };
-      case 4:
-        return {
-          level: "Hard",
-          time: "~1-5min",
-          color: "from-orange-500/20 to-red-500/20",
-          border: "border-orange-500/30",
-        };
-      case 5:
-        return {
-          level: "Very Hard",
-          time: "~5-15min",
-          color: "from-red-500/20 to-pink-500/20",
-          border: "border-red-500/30",
-        };
-      case 6:
-        return {
-          level: "Extreme",
-          time: "~15min+",
-          color: "from-purple-500/20 to-indigo-500/20",
-          border: "border-purple-500/30",
-        };
-      default:
-        return {
-          level: "Unknown",
-          time: "?",
-          color: "from-gray-500/20 to-slate-500/20",
-          border: "border-gray-500/30",
-        };
+    if (expectedHashes < 1e7) {
+      return {
+        level: "Medium",
+        color: "from-yellow-500/20 to-orange-500/20",
+        border: "border-yellow-500/30",
+      };
     }
+    return {
+      level: "Hard",
+      color: "from-red-500/20 to-pink-500/20",
+      border: "border-red-500/30",
+    };
   };
 
-  const currentDiffInfo = getDifficultyInfo(currentDifficulty);
+  const formatBits = (bits) =>
+    bits === undefined ? "?" : `0x${bits.toString(16).padStart(8, "0")}`;
+
+  const currentDiffInfo = getDifficultyInfo(difficulty?.expected_hashes);
 
   return (
     <div className="relative overflow-hidden rounded-2xl backdrop-blur-xl bg-white/10 border border-white/20 p-6">
@@ -241,7 +184,7 @@ const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
                 className={`px-3 py-1 rounded-lg text-xs font-medium backdrop-blur-sm border ${currentDiffInfo.color} ${currentDiffInfo.border}`}
               >
                 <div className="text-white">
-                  Difficulty: {currentDifficulty}
+                  Difficulty: {formatBits(difficulty?.current_difficulty)}
                 </div>
                 <div className="text-gray-300">{currentDiffInfo.level}</div>
               </div>
@@ -250,13 +193,16 @@ const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
             <div className="mb-4 p-3 rounded-lg bg-white/10 backdrop-blur-sm border border-white/20">
               <div className="text-sm text-gray-300 mb-2">
                 <span className="font-medium text-gray-200">
-                  Current Mining Difficulty:
+                  Expected Hashes per Block:
                 </span>{" "}
-                {currentDifficulty}/6
+                {difficulty
+                  ? Math.round(difficulty.expected_hashes).toLocaleString()
+                  : "?"}
               </div>
               <div className="text-xs text-gray-400">
-                <span className="font-medium">Estimated Time:</span>{" "}
-                {currentDiffInfo.time} |
+                <span className="font-medium">Retarget:</span> every{" "}
+                {difficulty?.retarget_interval ?? "?"} blocks toward{" "}
+                {difficulty?.target_block_seconds ?? "?"}s per block |
                 <span className="font-medium"> Level:</span>{" "}
                 {currentDiffInfo.level}
               </div>
@@ -288,110 +234,8 @@ const MiningActions = ({ refreshTrigger, onMiningComplete }) => {
               </span>
             </button>
           </div>
-
-          {/* Difficulty Control Section */}
-          <form
-            onSubmit={handleSetDifficulty}
-            className="p-6 rounded-xl bg-gradient-to-r from-teal-500/20 to-green-500/20 border border-teal-500/30 backdrop-blur-sm"
-          >
-            <h3 className="text-lg font-semibold text-white mb-4 flex items-center gap-2">
-              <span>⚙️</span>
-              <span>Mining Difficulty Settings</span>
-            </h3>
-
-            <div className="mb-4">
-              <label className="block text-sm font-medium text-gray-200 mb-2">
-                Select Difficulty Level (1-6)
-              </label>
-              <div className="flex items-center gap-4 mb-3">
-                <input
-                  type="range"
-                  min="1"
-                  max="6"
-                  value={difficulty}
-                  onChange={(e) => setDifficulty(parseInt(e.target.value))}
-                  className="flex-1 h-2 bg-white/20 rounded-lg appearance-none cursor-pointer slider"
-                  disabled={loading || !isConnected}
-                />
-                <div className="text-white font-bold text-lg min-w-[2rem] text-center">
-                  {difficulty}
-                </div>
-              </div>
-
-              {/* Difficulty Preview */}
-              <div
-                className={`p-3 rounded-lg backdrop-blur-sm border transition-all duration-300 ${
-                  getDifficultyInfo(difficulty).color
-                } ${getDifficultyInfo(difficulty).border}`}
-              >
-                <div className="text-sm text-white">
-                  <div className="font-medium">
-                    {getDifficultyInfo(difficulty).level}
-                  </div>
-                  <div className="text-xs text-gray-300">
-                    Expected mining time: {getDifficultyInfo(difficulty).time}
-                  </div>
-                </div>
-              </div>
-            </div>
-
-            <button
-              type="submit"
-              disabled={
-                loading || difficulty === currentDifficulty || !isConnected
-              }
-              className="relative overflow-hidden bg-gradient-to-r from-teal-500 to-green-600 text-white px-6 py-2 rounded-xl font-medium transition-all duration-300 hover:scale-105 hover:shadow-lg hover:shadow-teal-500/25 disabled:opacity-50 disabled:cursor-not-allowed flex items-center gap-2 group"
-            >
-              <div className="absolute inset-0 bg-gradient-to-r from-teal-600 to-green-700 opacity-0 group-hover:opacity-100 transition-opacity duration-300"></div>
-              <span className="relative z-10">{loading ? "⏳" : "⚙️"}</span>
-              <span className="relative z-10">
-                {loading
-                  ? "Updating..."
-                  : !isConnected
-                  ? "Connect Wallet"
-                  : difficulty === currentDifficulty
-                  ? "Current Setting"
-                  : "Update Difficulty"}
-              </span>
-            </button>
-
-            <div className="mt-3 text-xs text-gray-400 bg-white/10 p-3 rounded-lg backdrop-blur-sm border border-white/20">
-              <div className="font-medium text-gray-300 mb-1">
-                💡 Difficulty Guide:
-              </div>
-              <div>
-                Higher difficulty = More secure blocks + Longer mining time
-              </div>
-              <div>
-                Lower difficulty = Faster mining + Less computational work
-              </div>
-            </div>
-          </form>
         </div>
       </div>
-
-      <style jsx>{`
-        .slider::-webkit-slider-thumb {
-          appearance: none;
-          height: 20px;
-          width: 20px;
-          border-radius: 50%;
-          background: linear-gradient(45deg, #06b6d4, #10b981);
-          cursor: pointer;
-          border: 2px solid white;
-          box-shadow: 0 4px 8px rgba(0, 0, 0, 0.3);
-        }
-
-        .slider::-moz-range-thumb {
-          height: 20px;
-          width: 20px;
-          border-radius: 50%;
-          background: linear-gradient(45deg, #06b6d4, #10b981);
-          cursor: pointer;
-          border: 2px solid white;
-          box-shadow: 0 4px 8px rgba(0, 0, 0, 0.3);
-        }
-      `}</style>
     </div>
   );
 };
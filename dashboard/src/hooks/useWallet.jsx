@@ -87,8 +87,25 @@ export const WalletProvider = ({ children }) => {
       const { nonce } = await nonceResponse.json();
       console.log("Received nonce:", nonce);
 
-      // Create message to sign
-      const message = `Please sign this message to authenticate with Hikmalayer.\n\nNonce: ${nonce}\nAddress: ${userAccount}`;
+      // Create an EIP-4361 (Sign-In with Ethereum) message to sign
+      const chainIdHex = await window.ethereum.request({
+        method: "eth_chainId",
+      });
+      const issuedAt = new Date();
+      const expiresAt = new Date(issuedAt.getTime() + 5 * 60 * 1000);
+      const message = [
+        `${window.location.host} wants you to sign in with your Ethereum account:`,
+        userAccount,
+        "",
+        "Please sign this message to authenticate with Hikmalayer.",
+        "",
+        `URI: ${window.location.origin}`,
+        "Version: 1",
+        `Chain ID: ${parseInt(chainIdHex, 16)}`,
+        `Nonce: ${nonce}`,
+        `Issued At: ${issuedAt.toISOString()}`,
+        `Expiration Time: ${expiresAt.toISOString()}`,
+      ].join("\n");
 
       // Request signature from MetaMask
       const signature = await window.ethereum.request({
@@ -0,0 +1,223 @@
+#!/usr/bin/env python3
+"""Sign in to a Hikmalayer node with a local dev key.
+
+Builds an EIP-4361 (Sign-In with Ethereum) message for the nonce from
+/auth/nonce, signs it the way MetaMask's personal_sign does and exchanges it
+at /auth/verify for a bearer token. Only meant for testnet and benchmark keys:
+the key is passed on the command line and the signing code is not hardened.
+
+    python3 ops/siwe_login.py address <key>
+    python3 ops/siwe_login.py public-key <key>
+    python3 ops/siwe_login.py token <base-url> <key>
+
+The message must match the node's SIWE_DOMAIN, SIWE_URI and SIWE_CHAIN_ID,
+read here from the same environment variables with the node's defaults.
+"""
+import argparse
+import hashlib
+import hmac
+import json
+import os
+import sys
+from datetime import datetime, timezone
+from urllib import request
+
+# secp256k1 curve parameters
+P = 2**256 - 2**32 - 977
+N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+G = (
+    0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798,
+    0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8,
+)
+
+KECCAK_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+KECCAK_ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+MASK_64 = 2**64 - 1
+
+
+def _rotl(value, shift):
+    return ((value << shift) | (value >> (64 - shift))) & MASK_64 if shift else value
+
+
+def _keccak_f(lanes):
+    for round_constant in KECCAK_ROUND_CONSTANTS:
+        c = [lanes[x][0] ^ lanes[x][1] ^ lanes[x][2] ^ lanes[x][3] ^ lanes[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ _rotl(c[(x + 1) % 5], 1) for x in range(5)]
+        lanes = [[lanes[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = _rotl(lanes[x][y], KECCAK_ROTATIONS[x][y])
+        lanes = [
+            [b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)]
+            for x in range(5)
+        ]
+        lanes[0][0] ^= round_constant
+    return lanes
+
+
+def keccak256(data):
+    """Ethereum's Keccak-256, which differs from hashlib's SHA3-256 in padding."""
+    rate = 136
+    padded = bytearray(data) + b"\x01" + b"\x00" * ((-len(data) - 1) % rate)
+    padded[-1] |= 0x80
+    lanes = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(padded), rate):
+        block = padded[offset : offset + rate]
+        for i in range(rate // 8):
+            lanes[i % 5][i // 5] ^= int.from_bytes(block[i * 8 : i * 8 + 8], "little")
+        lanes = _keccak_f(lanes)
+    return b"".join(lanes[i % 5][i // 5].to_bytes(8, "little") for i in range(4))
+
+
+def _point_add(a, b):
+    if a is None:
+        return b
+    if b is None:
+        return a
+    if a[0] == b[0] and (a[1] + b[1]) % P == 0:
+        return None
+    if a == b:
+        slope = 3 * a[0] * a[0] * pow(2 * a[1], -1, P)
+    else:
+        slope = (b[1] - a[1]) * pow(b[0] - a[0], -1, P)
+    x = (slope * slope - a[0] - b[0]) % P
+    return x, (slope * (a[0] - x) - a[1]) % P
+
+
+def _point_mul(scalar, point=G):
+    result = None
+    while scalar:
+        if scalar & 1:
+            result = _point_add(result, point)
+        point = _point_add(point, point)
+        scalar >>= 1
+    return result
+
+
+def _secret(key_hex):
+    secret = int(key_hex.removeprefix("0x"), 16)
+    if not 0 < secret < N:
+        raise ValueError("private key out of range")
+    return secret
+
+
+def public_key(key_hex):
+    """Uncompressed public key hex, as /staking/deposit expects it."""
+    x, y = _point_mul(_secret(key_hex))
+    return "04" + x.to_bytes(32, "big").hex() + y.to_bytes(32, "big").hex()
+
+
+def address(key_hex):
+    """Lowercase 0x address of the key; balances and roles are keyed by it."""
+    return "0x" + keccak256(bytes.fromhex(public_key(key_hex))[1:])[12:].hex()
+
+
+def _nonce_k(secret, digest):
+    # RFC 6979 deterministic nonce, so signatures match ethers/MetaMask
+    x = secret.to_bytes(32, "big")
+    v = b"\x01" * 32
+    k = b"\x00" * 32
+    k = hmac.new(k, v + b"\x00" + x + digest, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    k = hmac.new(k, v + b"\x01" + x + digest, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    while True:
+        v = hmac.new(k, v, hashlib.sha256).digest()
+        candidate = int.from_bytes(v, "big")
+        if 0 < candidate < N:
+            return candidate
+        k = hmac.new(k, v + b"\x00", hashlib.sha256).digest()
+        v = hmac.new(k, v, hashlib.sha256).digest()
+
+
+def personal_sign(key_hex, message):
+    """EIP-191 signature over `message` as 0x-prefixed r || s || v."""
+    data = message.encode("utf-8")
+    digest = keccak256(b"\x19Ethereum Signed Message:\n" + str(len(data)).encode() + data)
+    secret = _secret(key_hex)
+    z = int.from_bytes(digest, "big")
+    k = _nonce_k(secret, digest)
+    rx, ry = _point_mul(k)
+    r = rx % N
+    s = pow(k, -1, N) * (z + r * secret) % N
+    recovery = (ry & 1) | (2 if rx >= N else 0)
+    if s > N // 2:
+        s = N - s
+        recovery ^= 1
+    return "0x" + r.to_bytes(32, "big").hex() + s.to_bytes(32, "big").hex() + f"{27 + recovery:02x}"
+
+
+def _post(url, payload):
+    req = request.Request(
+        url,
+        data=json.dumps(payload).encode("utf-8"),
+        headers={"Content-Type": "application/json"},
+    )
+    with request.urlopen(req, timeout=10) as resp:
+        return json.loads(resp.read().decode("utf-8"))
+
+
+def sign_in(base_url, key_hex):
+    """Returns a bearer token for the key's address."""
+    account = address(key_hex)
+    nonce = _post(f"{base_url}/auth/nonce", {"address": account})["nonce"]
+    domain = os.environ.get("SIWE_DOMAIN", "localhost:5173")
+    uri = os.environ.get("SIWE_URI", f"http://{domain}")
+    chain_id = os.environ.get("SIWE_CHAIN_ID", "1")
+    issued_at = datetime.now(timezone.utc).strftime("%Y-%m-%dT%H:%M:%SZ")
+    message = (
+        f"{domain} wants you to sign in with your Ethereum account:\n"
+        f"{account}\n\n"
+        "Sign in to Hikmalayer.\n\n"
+        f"URI: {uri}\n"
+        "Version: 1\n"
+        f"Chain ID: {chain_id}\n"
+        f"Nonce: {nonce}\n"
+        f"Issued At: {issued_at}"
+    )
+    session = _post(
+        f"{base_url}/auth/verify",
+        {
+            "address": account,
+            "message": message,
+            "signature": personal_sign(key_hex, message),
+            "nonce": nonce,
+        },
+    )
+    return session["token"]
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.split("\n\n")[0])
+    commands = parser.add_subparsers(dest="command", required=True)
+    commands.add_parser("address").add_argument("key")
+    commands.add_parser("public-key").add_argument("key")
+    token = commands.add_parser("token")
+    token.add_argument("base_url")
+    token.add_argument("key")
+    args = parser.parse_args()
+
+    if args.command == "address":
+        print(address(args.key))
+    elif args.command == "public-key":
+        print(public_key(args.key))
+    else:
+        print(sign_in(args.base_url, args.key))
+
+
+if __name__ == "__main__":
+    sys.exit(main())
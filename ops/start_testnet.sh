@@ -2,6 +2,19 @@
 set -euo pipefail
 
 COMPOSE_FILE="${COMPOSE_FILE:-docker-compose.yml}"
+P2P_TOKEN="${P2P_TOKEN:-local-testnet}"
+SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
+
+function siwe() {
+  python3 "${SCRIPT_DIR}/siwe_login.py" "$@"
+}
+
+# Well-known dev keys; never reuse them outside a local testnet
+OPERATOR_KEY="${OPERATOR_KEY:-0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80}"
+OPERATOR_ADDRESS=$(siwe address "${OPERATOR_KEY}")
+# The operator is every node's bootstrap admin (see docker-compose.yml)
+export ADMIN_ADDRESSES="${ADMIN_ADDRESSES:-${OPERATOR_ADDRESS}}"
+OPERATOR_FUNDS="${OPERATOR_FUNDS:-2000}"
 
 echo "Starting Hikmalayer testnet..."
 docker compose -f "${COMPOSE_FILE}" up -d --build
@@ -9,23 +22,42 @@ docker compose -f "${COMPOSE_FILE}" up -d --build
 echo "Waiting for nodes to boot..."
 sleep 5
 
-ADMIN_TOKEN="${ADMIN_TOKEN:-local-admin}"
-P2P_TOKEN="${P2P_TOKEN:-local-testnet}"
-
-VALIDATOR_KEY_PRIV="0000000000000000000000000000000000000000000000000000000000000001"
-VALIDATOR_KEY_PUB="0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+function post() {
+  local url=$1
+  local token=$2
+  local body=$3
+  curl -sSf -X POST "${url}" \
+    -H "Content-Type: application/json" \
+    -H "Authorization: Bearer ${token}" \
+    -d "${body}" >/dev/null
+}
 
 function seed_validator() {
   local node_url=$1
-  local address=$2
+  local validator_key=$2
+  local address
+  local public_key
+  local operator_token
+  local validator_token
+  address=$(siwe address "${validator_key}")
+  public_key=$(siwe public-key "${validator_key}")
 
-  curl -s -X POST "${node_url}/tokens/transfer" \
-    -H "Content-Type: application/json" \
-    -d "{\"from\":\"admin\",\"to\":\"${address}\",\"amount\":100}" >/dev/null
+  # As admin: fund the operator, then let the validator stake
+  operator_token=$(siwe token "${node_url}" "${OPERATOR_KEY}")
+  post "${node_url}/governance/mint-authorities" "${operator_token}" \
+    "{\"account\":\"${OPERATOR_ADDRESS}\",\"allowed\":true}"
+  post "${node_url}/tokens/native/mint" "${operator_token}" \
+    "{\"to\":\"${OPERATOR_ADDRESS}\",\"amount\":${OPERATOR_FUNDS}}"
+  post "${node_url}/rbac/grant" "${operator_token}" \
+    "{\"address\":\"${address}\",\"role\":\"validator_operator\"}"
 
-  curl -s -X POST "${node_url}/staking/deposit" \
-    -H "Content-Type: application/json" \
-    -d "{\"address\":\"${address}\",\"amount\":100,\"public_key\":\"${VALIDATOR_KEY_PUB}\",\"private_key\":\"${VALIDATOR_KEY_PRIV}\"}" >/dev/null
+  # Transfers and stakes are signed by a session for the paying address
+  post "${node_url}/tokens/transfer" "${operator_token}" \
+    "{\"from\":\"${OPERATOR_ADDRESS}\",\"to\":\"${address}\",\"amount\":100}"
+
+  validator_token=$(siwe token "${node_url}" "${validator_key}")
+  post "${node_url}/staking/deposit" "${validator_token}" \
+    "{\"address\":\"${address}\",\"amount\":100,\"public_key\":\"${public_key}\",\"private_key\":\"${validator_key#0x}\"}"
 
   curl -s -X POST "${node_url}/p2p/peers/register" \
     -H "Content-Type: application/json" \
@@ -33,10 +65,10 @@ function seed_validator() {
     -d "{\"address\":\"http://bootnode:3000\"}" >/dev/null || true
 }
 
-seed_validator "http://localhost:3000" "validator-boot"
-seed_validator "http://localhost:3001" "validator-1"
-seed_validator "http://localhost:3002" "validator-2"
-seed_validator "http://localhost:3003" "validator-3"
-seed_validator "http://localhost:3004" "validator-4"
+seed_validator "http://localhost:3000" "0x0000000000000000000000000000000000000000000000000000000000000001"
+seed_validator "http://localhost:3001" "0x0000000000000000000000000000000000000000000000000000000000000002"
+seed_validator "http://localhost:3002" "0x0000000000000000000000000000000000000000000000000000000000000003"
+seed_validator "http://localhost:3003" "0x0000000000000000000000000000000000000000000000000000000000000004"
+seed_validator "http://localhost:3004" "0x0000000000000000000000000000000000000000000000000000000000000005"
 
 echo "Testnet started."
@@ -0,0 +1,276 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::blockchain::chain::ChainError;
+use crate::contract::error::{CertificateError, ContractError};
+use crate::token::{error::TokenError, nft::NftError};
+
+/// An error returned by a handler: an HTTP status plus a stable,
+/// machine-readable `code` that clients can branch on. The `message` is for
+/// humans and may change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Result of a handler that answers with JSON on success.
+pub type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    status: &'static str,
+    code: &'static str,
+    message: &'a str,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized", message)
+    }
+
+    pub fn forbidden(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, code, message)
+    }
+
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, message)
+    }
+
+    pub fn conflict(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, code, message)
+    }
+
+    /// A well-formed request that the current state cannot satisfy, such as
+    /// spending more than a balance.
+    pub fn unprocessable(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", message)
+    }
+
+    /// The request names an account other than the one signed in.
+    pub fn session_mismatch(field: &str) -> Self {
+        Self::forbidden(
+            "session_mismatch",
+            format!("{} must match the signed-in wallet", field),
+        )
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            status: "error",
+            code: self.code,
+            message: &self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+impl From<TokenError> for ApiError {
+    fn from(err: TokenError) -> Self {
+        let message = err.to_string();
+        match err {
+            TokenError::UnknownToken(_) => ApiError::not_found("unknown_token", message),
+            TokenError::InvalidName => ApiError::bad_request("invalid_token_name", message),
+            TokenError::InvalidSymbol { .. } => {
+                ApiError::bad_request("invalid_token_symbol", message)
+            }
+            TokenError::InvalidVesting(_) => ApiError::bad_request("invalid_vesting", message),
+            TokenError::SymbolTaken(_) => ApiError::conflict("token_symbol_taken", message),
+            TokenError::MaxSupplyBelowSupply { .. } => {
+                ApiError::conflict("max_supply_below_supply", message)
+            }
+            TokenError::NotTransferable(_) => {
+                ApiError::forbidden("token_not_transferable", message)
+            }
+            TokenError::NotMintAuthority { .. } => {
+                ApiError::forbidden("not_mint_authority", message)
+            }
+            TokenError::NotTokenOwner { .. } => ApiError::forbidden("not_token_owner", message),
+            TokenError::NativeMintersGoverned => {
+                ApiError::forbidden("native_token_governed", message)
+            }
+            TokenError::InsufficientBalance { .. } => {
+                ApiError::unprocessable("insufficient_balance", message)
+            }
+            TokenError::InsufficientAllowance { .. } => {
+                ApiError::unprocessable("insufficient_allowance", message)
+            }
+            TokenError::BalanceOverflow { .. } => {
+                ApiError::unprocessable("balance_overflow", message)
+            }
+            TokenError::SupplyUnderflow { .. } => {
+                ApiError::unprocessable("supply_underflow", message)
+            }
+            TokenError::MaxSupplyExceeded { .. } => {
+                ApiError::unprocessable("max_supply_exceeded", message)
+            }
+        }
+    }
+}
+
+impl From<NftError> for ApiError {
+    fn from(err: NftError) -> Self {
+        let message = err.to_string();
+        match err {
+            NftError::UnknownNft(_) => ApiError::not_found("unknown_nft", message),
+            NftError::NotOwner { .. } => ApiError::forbidden("not_nft_owner", message),
+            NftError::Soulbound(_) => ApiError::forbidden("nft_soulbound", message),
+            NftError::InvalidMetadataUri { .. } => {
+                ApiError::bad_request("invalid_metadata_uri", message)
+            }
+        }
+    }
+}
+
+impl From<CertificateError> for ApiError {
+    fn from(err: CertificateError) -> Self {
+        let message = err.to_string();
+        match err {
+            CertificateError::Invalid(_) => ApiError::bad_request("invalid_certificate", message),
+            CertificateError::IssuerMismatch(_) => {
+                ApiError::bad_request("issuer_mismatch", message)
+            }
+            CertificateError::EmptyBatch => ApiError::bad_request("empty_batch", message),
+            CertificateError::InvalidRewardPolicy(_) => {
+                ApiError::bad_request("invalid_reward_policy", message)
+            }
+            CertificateError::NotFound(_) => ApiError::not_found("certificate_not_found", message),
+            CertificateError::AlreadyExists(_) => ApiError::conflict("certificate_exists", message),
+            CertificateError::BatchExists(_) => ApiError::conflict("batch_exists", message),
+            CertificateError::InvalidTransition { .. } => {
+                ApiError::conflict("invalid_status_transition", message)
+            }
+            CertificateError::NotActive { .. } => {
+                ApiError::conflict("certificate_not_active", message)
+            }
+            CertificateError::UntrustedSignature { .. } => {
+                ApiError::conflict("certificate_not_verified", message)
+            }
+            CertificateError::RewardBudgetExhausted(_) => {
+                ApiError::unprocessable("reward_budget_exhausted", message)
+            }
+            CertificateError::Token(err) => ApiError {
+                message,
+                ..err.into()
+            },
+        }
+    }
+}
+
+impl From<ContractError> for ApiError {
+    fn from(err: ContractError) -> Self {
+        let message = err.to_string();
+        match err {
+            ContractError::CodeTooLarge { .. } => {
+                ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "contract_too_large", message)
+            }
+            ContractError::InvalidWasm(_) => ApiError::bad_request("invalid_wasm", message),
+            ContractError::GasLimitTooHigh { .. } | ContractError::GasLimitTooLow { .. } => {
+                ApiError::bad_request("invalid_gas_limit", message)
+            }
+            ContractError::NotFound(_) => ApiError::not_found("contract_not_found", message),
+            ContractError::UnknownFunction { .. } => {
+                ApiError::not_found("unknown_function", message)
+            }
+            ContractError::AlreadyDeployed(_) => ApiError::conflict("contract_exists", message),
+            ContractError::InsufficientFunds { .. } => {
+                ApiError::unprocessable("insufficient_balance", message)
+            }
+        }
+    }
+}
+
+impl From<ChainError> for ApiError {
+    fn from(err: ChainError) -> Self {
+        let message = err.to_string();
+        match err {
+            ChainError::IndexMismatch { .. } => ApiError::conflict("block_index_mismatch", message),
+            ChainError::PreviousHashMismatch => {
+                ApiError::conflict("previous_hash_mismatch", message)
+            }
+            ChainError::DifficultyMismatch { .. } => {
+                ApiError::bad_request("difficulty_mismatch", message)
+            }
+            ChainError::InvalidDifficulty(_) => {
+                ApiError::bad_request("invalid_difficulty", message)
+            }
+            ChainError::InvalidTimestamp => ApiError::bad_request("invalid_timestamp", message),
+            ChainError::MissingValidatorData(_) => {
+                ApiError::bad_request("missing_validator_data", message)
+            }
+            ChainError::StakerSetHashMismatch => {
+                ApiError::bad_request("staker_set_mismatch", message)
+            }
+            ChainError::WrongValidator => ApiError::bad_request("wrong_validator", message),
+            ChainError::InvalidSignature => {
+                ApiError::bad_request("invalid_block_signature", message)
+            }
+            ChainError::InvalidPow => ApiError::bad_request("invalid_pow", message),
+            ChainError::GenesisNotSlashable => {
+                ApiError::bad_request("genesis_not_slashable", message)
+            }
+            ChainError::BlockNotFound(_) => ApiError::not_found("block_not_found", message),
+            ChainError::NotSlashable => ApiError::unprocessable("not_slashable", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_errors_map_to_status_and_code() {
+        let err: ApiError = TokenError::UnknownToken("XYZ".to_string()).into();
+        assert_eq!(
+            (err.status, err.code),
+            (StatusCode::NOT_FOUND, "unknown_token")
+        );
+
+        // A reward the issuer cannot fund keeps the token's code
+        let err: ApiError = CertificateError::Token(TokenError::InsufficientBalance {
+            symbol: "MCT".to_string(),
+            account: "0xuni".to_string(),
+        })
+        .into();
+        assert_eq!(
+            (err.status, err.code),
+            (StatusCode::UNPROCESSABLE_ENTITY, "insufficient_balance")
+        );
+        assert!(err.message.starts_with("Reward could not be paid"));
+
+        let response = ApiError::session_mismatch("issuer").into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -1 +1,3 @@
+pub mod error;
 pub mod routes;
+pub mod stream;
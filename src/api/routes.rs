@@ -1,48 +1,96 @@
 use axum::{
-    extract::{Path, State},
-    http::HeaderMap,
-    response::Json,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
+    response::{IntoResponse, Json},
     routing::{get, post},
-    Router,
+    Extension, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path as FsPath, PathBuf};
+use std::sync::{atomic::AtomicBool, Arc};
+use tokio::sync::{oneshot, RwLock};
 
+use super::error::{ApiError, ApiResult};
 use crate::{
-    auth::AuthManager,
+    auth::{
+        middleware::{auth_middleware, role_middleware, AuthenticatedAddress, RoleGuard},
+        rbac::{Role, RoleRegistry},
+        AuthManager,
+    },
     blockchain::{
-        block::Block,
+        block::{Block, BlockHeader, BlockTransaction},
         chain::Blockchain,
+        receipt::{LogEntry, LogQuery, Receipt, ReceiptStore},
         transaction::{Transaction, TransactionType},
     },
-    consensus::pos::{self, Staker},
-    contract::contract::ContractExecutor,
+    consensus::{
+        difficulty,
+        mining::{MiningJob, MiningJobs, MiningStatus},
+        pos::{self, Staker},
+        pow,
+    },
+    contract::{
+        contract::{
+            BlockAnchor, Certificate, CertificateQuery, CertificateReceipt, CertificateReward,
+            CertificateStatus, ContractExecutor, IssuerSignature, RewardPolicy, SignatureCheck,
+            StatusChange, StatusReason,
+        },
+        credential::VerifiableCredential,
+        error::CertificateError,
+        issuers::{IssuerRegistry, TrustedIssuer},
+        runtime::{ExecutionOutcome, Invocation, WasmContract},
+    },
+    events::{ChainEvent, EventBus, EventLog, NFT_CONTRACT, TOKEN_CONTRACT},
     governance::GovernanceConfig,
     p2p::{
         protocol::{P2PEnvelope, P2PPayload},
         service::P2PService,
     },
-    persistence::{save_state, AppSnapshot},
-    token::fungible::Token,
+    persistence::{save_state, AppSnapshot, PersistSignal},
+    token::{
+        nft::{Nft, NftRegistry},
+        registry::{RegisteredToken, TokenRegistry, NATIVE_TOKEN},
+        vesting::VestingSchedule,
+    },
 };
 
 const STAKING_POOL_ACCOUNT: &str = "__staking_pool__";
-
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
+const MAX_CERTIFICATE_BATCH_SIZE: usize = 10_000;
+const REVOCATION_LIST_CACHE_CONTROL: &str = "public, max-age=60";
+
+/// Shared node state, one lock per component so unrelated requests do not
+/// contend. Read-only handlers take read locks.
+///
+/// Lock order: code that holds several locks at once acquires them in the
+/// order the fields are declared here, `chain` first and `metrics` last, and
+/// never awaits network I/O while holding any. Handlers do not write the
+/// snapshot themselves; [`persist_state`] only signals [`run_persister`], so it
+/// may be called with locks held.
 #[derive(Clone)]
 pub struct AppState {
-    pub chain: Arc<Mutex<Blockchain>>,
-    pub token: Arc<Mutex<Token>>,
-    pub contracts: Arc<Mutex<ContractExecutor>>,
-    pub pending_transactions: Arc<Mutex<Vec<Transaction>>>,
-    pub auth_manager: Arc<Mutex<AuthManager>>,
-    pub stakers: Arc<Mutex<Vec<Staker>>>,
-    pub peers: Arc<Mutex<Vec<String>>>,
-    pub governance: Arc<Mutex<GovernanceConfig>>,
-    pub slash_evidence: Arc<Mutex<Vec<crate::persistence::SlashEvidence>>>,
-    pub metrics: Arc<Mutex<Metrics>>,
+    pub chain: Arc<RwLock<Blockchain>>,
+    pub tokens: Arc<RwLock<TokenRegistry>>,
+    pub contracts: Arc<RwLock<ContractExecutor>>,
+    pub pending_transactions: Arc<RwLock<Vec<Transaction>>>,
+    pub auth_manager: Arc<RwLock<AuthManager>>,
+    pub stakers: Arc<RwLock<Vec<Staker>>>,
+    pub peers: Arc<RwLock<Vec<String>>>,
+    pub governance: Arc<RwLock<GovernanceConfig>>,
+    pub slash_evidence: Arc<RwLock<Vec<crate::persistence::SlashEvidence>>>,
+    pub roles: Arc<RwLock<RoleRegistry>>,
+    pub issuers: Arc<RwLock<IssuerRegistry>>,
+    pub receipts: Arc<RwLock<ReceiptStore>>,
+    pub nfts: Arc<RwLock<NftRegistry>>,
+    pub mining: Arc<RwLock<MiningJobs>>,
+    pub metrics: Arc<RwLock<Metrics>>,
+    pub events: EventBus,
+    pub persist: PersistSignal,
     pub p2p_token: Option<String>,
-    pub admin_token: Option<String>,
     pub p2p_service: Arc<P2PService>,
 }
 
@@ -51,6 +99,91 @@ pub struct CertificateRequest {
     pub id: String,
     pub issued_to: String,
     pub description: String,
+    /// Defaults to the authenticated session address when omitted.
+    pub issuer: Option<String>,
+    pub credential_type: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    pub content_hash: Option<String>,
+    /// Issuer signature over the certificate's signing payload
+    /// (see `/certificates/signing-payload`).
+    pub signature: Option<IssuerSignature>,
+    /// Also mint a soulbound NFT of the certificate to its holder.
+    #[serde(default)]
+    pub mint_nft: bool,
+}
+
+#[derive(Deserialize)]
+pub struct BatchCertificateRequest {
+    pub certificates: Vec<CertificateRequest>,
+}
+
+#[derive(Serialize)]
+pub struct BatchIssueResponse {
+    pub status: String,
+    pub message: String,
+    pub merkle_root: Option<String>,
+    /// One receipt per certificate, to hand to each holder. Receipts gain an
+    /// `anchor` once the batch is mined (see `/certificates/{id}/receipt`).
+    pub receipts: Vec<CertificateReceipt>,
+}
+
+#[derive(Serialize)]
+pub struct ReceiptResponse {
+    pub status: String,
+    pub message: String,
+    pub receipt: Option<CertificateReceipt>,
+}
+
+#[derive(Serialize)]
+pub struct ReceiptVerificationResponse {
+    pub status: String,
+    pub message: String,
+    /// The payload hashes to the target hash and the proof leads to the root.
+    pub proof_valid: bool,
+    /// The root was anchored by a batch transaction on this chain.
+    pub root_anchored: bool,
+    pub certificate_status: Option<CertificateStatus>,
+}
+
+#[derive(Serialize)]
+pub struct SigningPayloadResponse {
+    pub status: String,
+    pub message: String,
+    pub payload: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CredentialExportResponse {
+    pub status: String,
+    pub message: String,
+    pub credential: Option<VerifiableCredential>,
+}
+
+#[derive(Serialize)]
+pub struct CredentialVerificationResponse {
+    pub status: String,
+    pub message: String,
+    pub certificate_id: Option<String>,
+    pub signature: Option<SignatureCheck>,
+    /// The proof's block exists on this chain and contains the anchoring transaction.
+    pub anchored: bool,
+    /// The credential matches the certificate recorded on this chain.
+    pub matches_record: bool,
+    pub certificate_status: Option<CertificateStatus>,
+}
+
+#[derive(Deserialize)]
+pub struct IssuerRequest {
+    pub address: String,
+    pub public_key: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RemoveIssuerRequest {
+    pub address: String,
 }
 
 #[derive(Deserialize)]
@@ -58,6 +191,94 @@ pub struct VerifyCertificateRequest {
     pub id: String,
 }
 
+#[derive(Deserialize)]
+pub struct CertificateStatusRequest {
+    pub id: String,
+    pub reason: StatusReason,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CertificateVerificationResponse {
+    pub status: String,
+    pub message: String,
+    /// `None` when the certificate does not exist.
+    pub certificate_status: Option<CertificateStatus>,
+    pub status_history: Vec<StatusChange>,
+    /// `None` when the certificate does not exist.
+    pub signature: Option<SignatureCheck>,
+    /// The holder's reward, if one has been paid.
+    pub reward: Option<CertificateReward>,
+}
+
+#[derive(Deserialize)]
+pub struct RewardClaimRequest {
+    pub id: String,
+}
+
+#[derive(Serialize)]
+pub struct RewardClaimResponse {
+    pub status: String,
+    pub message: String,
+    /// `None` when no reward policy covers the certificate.
+    pub reward: Option<CertificateReward>,
+}
+
+#[derive(Deserialize)]
+pub struct RewardPolicyRequest {
+    pub credential_type: String,
+    pub amount: u64,
+    pub budget: u64,
+}
+
+#[derive(Serialize)]
+pub struct RevocationEntry {
+    pub id: String,
+    pub issuer: String,
+    pub status: CertificateStatus,
+    pub reason: Option<StatusReason>,
+    pub changed_at: Option<DateTime<Utc>>,
+    pub anchor: Option<BlockAnchor>,
+}
+
+#[derive(Serialize)]
+pub struct RevocationListResponse {
+    pub generated_at: DateTime<Utc>,
+    pub entries: Vec<RevocationEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct ContractDeployRequest {
+    /// Hex-encoded WASM module.
+    pub code: String,
+    /// Hex-encoded input for the `init` export.
+    #[serde(default)]
+    pub input: String,
+    #[serde(default)]
+    pub value: u64,
+    pub gas_limit: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ContractCallRequest {
+    pub contract: String,
+    pub function: String,
+    /// Hex-encoded input.
+    #[serde(default)]
+    pub input: String,
+    #[serde(default)]
+    pub value: u64,
+    pub gas_limit: u64,
+}
+
+#[derive(Serialize)]
+pub struct ContractExecutionResponse {
+    pub status: String,
+    pub message: String,
+    /// `None` when the request was rejected before the contract ran.
+    pub outcome: Option<ExecutionOutcome>,
+}
+
 #[derive(Deserialize)]
 pub struct TokenTransferRequest {
     pub from: String,
@@ -66,8 +287,161 @@ pub struct TokenTransferRequest {
 }
 
 #[derive(Deserialize)]
-pub struct DifficultyRequest {
-    pub difficulty: usize,
+pub struct TokenCreateRequest {
+    pub name: String,
+    pub symbol: String,
+    pub initial_supply: u64,
+    #[serde(default)]
+    pub max_supply: Option<u64>,
+    #[serde(default = "default_transferable")]
+    pub transferable: bool,
+}
+
+fn default_transferable() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub struct TokenMintRequest {
+    pub to: String,
+    pub amount: u64,
+}
+
+#[derive(Deserialize)]
+pub struct TokenBurnRequest {
+    pub from: String,
+    pub amount: u64,
+}
+
+#[derive(Deserialize)]
+pub struct TokenMinterRequest {
+    pub account: String,
+    pub allowed: bool,
+}
+
+#[derive(Deserialize)]
+pub struct VestingGrantRequest {
+    pub beneficiary: String,
+    pub amount: u64,
+    /// Defaults to now.
+    pub start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+#[derive(Serialize)]
+pub struct VestingScheduleStatus {
+    #[serde(flatten)]
+    pub schedule: VestingSchedule,
+    pub ends_at: DateTime<Utc>,
+    pub vested: u64,
+    pub unvested: u64,
+}
+
+#[derive(Serialize)]
+pub struct VestingResponse {
+    pub token: String,
+    pub account: String,
+    pub balance: u64,
+    /// Balance that can be transferred now.
+    pub available: u64,
+    pub vested: u64,
+    pub unvested: u64,
+    pub schedules: Vec<VestingScheduleStatus>,
+}
+
+#[derive(Deserialize)]
+pub struct NftMintRequest {
+    pub to: String,
+    pub metadata_uri: String,
+    #[serde(default)]
+    pub soulbound: bool,
+}
+
+#[derive(Deserialize)]
+pub struct NftTransferRequest {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Deserialize)]
+pub struct MaxSupplyRequest {
+    /// `null` removes the cap.
+    pub max_supply: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenApproveRequest {
+    pub owner: String,
+    pub spender: String,
+    /// New allowance, replacing the old one; `0` revokes it.
+    pub amount: u64,
+}
+
+#[derive(Deserialize)]
+pub struct TokenTransferFromRequest {
+    pub owner: String,
+    pub to: String,
+    pub amount: u64,
+}
+
+#[derive(Serialize)]
+pub struct AllowanceResponse {
+    pub token: String,
+    pub owner: String,
+    pub spender: String,
+    pub allowance: u64,
+}
+
+#[derive(Deserialize)]
+pub struct IssuerFilterQuery {
+    pub issuer: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BlockPageQuery {
+    pub from: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+impl BlockPageQuery {
+    fn bounds(&self) -> (usize, usize) {
+        page_bounds(self.from, self.limit)
+    }
+}
+
+fn page_bounds(from: Option<usize>, limit: Option<usize>) -> (usize, usize) {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    (from.unwrap_or(0), limit)
+}
+
+#[derive(Deserialize)]
+pub struct CertificateListQuery {
+    pub holder: Option<String>,
+    pub issuer: Option<String>,
+    pub status: Option<CertificateStatus>,
+    pub from: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct CertificatePage {
+    /// Number of certificates matching the filters, across all pages.
+    pub total: usize,
+    pub from: usize,
+    pub limit: usize,
+    pub certificates: Vec<Certificate>,
+}
+
+/// Query keys of `GET /events` that are not attribute filters.
+const EVENT_QUERY_KEYS: [&str; 5] = ["type", "contract", "from_block", "to_block", "limit"];
+
+#[derive(Serialize)]
+pub struct EventQueryResponse {
+    pub status: String,
+    pub message: String,
+    pub events: Vec<LogEntry>,
 }
 
 #[derive(Deserialize)]
@@ -95,6 +469,18 @@ pub struct SlashEvidenceRequest {
     pub reporter: String,
 }
 
+#[derive(Deserialize)]
+pub struct RoleRequest {
+    pub address: String,
+    pub role: Role,
+}
+
+#[derive(Serialize)]
+pub struct RolesResponse {
+    pub address: String,
+    pub roles: Vec<Role>,
+}
+
 #[derive(Serialize)]
 pub struct ApiResponse {
     pub status: String,
@@ -103,6 +489,7 @@ pub struct ApiResponse {
 
 #[derive(Serialize)]
 pub struct BalanceResponse {
+    pub token: String,
     pub account: String,
     pub balance: u64,
 }
@@ -113,13 +500,16 @@ pub struct MiningResponse {
     pub message: String,
     pub block_index: u64,
     pub transactions_count: usize,
+    /// See `GET /mining/jobs/{id}`.
+    pub job_id: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct BlockchainStats {
     pub total_blocks: usize,
     pub pending_transactions: usize,
-    pub difficulty: usize,
+    /// Compact target of the next block.
+    pub difficulty: u32,
     pub is_valid: bool,
     pub latest_hash: String,
     pub finalized_height: u64,
@@ -136,7 +526,13 @@ pub struct ValidationResponse {
 
 #[derive(Serialize)]
 pub struct DifficultyResponse {
-    pub current_difficulty: usize,
+    /// Compact ("nBits") target the next block must meet.
+    pub current_difficulty: u32,
+    /// The same target as 64 hex digits.
+    pub target: String,
+    pub expected_hashes: f64,
+    pub target_block_seconds: i64,
+    pub retarget_interval: u64,
 }
 
 #[derive(Serialize)]
@@ -184,15 +580,27 @@ pub struct Metrics {
     pub protocol_messages_rejected: u64,
 }
 
-async fn persist_state(state: &AppState) -> Result<(), String> {
-    let chain = state.chain.lock().await;
-    let token = state.token.lock().await;
-    let contracts = state.contracts.lock().await;
-    let pending = state.pending_transactions.lock().await;
-    let stakers = state.stakers.lock().await;
-    let peers = state.peers.lock().await;
-    let governance = state.governance.lock().await;
-    let slash_evidence = state.slash_evidence.lock().await;
+/// Asks the background writer to save a snapshot. Never blocks.
+pub(crate) fn persist_state(state: &AppState) {
+    state.persist.request();
+}
+
+/// Copies every persisted component under read locks, taken in the order
+/// documented on [`AppState`].
+async fn snapshot_state(state: &AppState) -> AppSnapshot {
+    let chain = state.chain.read().await;
+    let tokens = state.tokens.read().await;
+    let contracts = state.contracts.read().await;
+    let pending = state.pending_transactions.read().await;
+    let auth_manager = state.auth_manager.read().await;
+    let stakers = state.stakers.read().await;
+    let peers = state.peers.read().await;
+    let governance = state.governance.read().await;
+    let slash_evidence = state.slash_evidence.read().await;
+    let roles = state.roles.read().await;
+    let issuers = state.issuers.read().await;
+    let receipts = state.receipts.read().await;
+    let nfts = state.nfts.read().await;
 
     let stakers_snapshot: Vec<Staker> = stakers
         .iter()
@@ -204,23 +612,55 @@ async fn persist_state(state: &AppState) -> Result<(), String> {
         })
         .collect();
 
-    let snapshot = AppSnapshot {
+    AppSnapshot {
         chain: chain.clone(),
-        token: token.clone(),
+        token: None,
+        tokens: Some(tokens.clone()),
         contracts: contracts.clone(),
         pending_transactions: pending.clone(),
         stakers: stakers_snapshot,
         peers: peers.clone(),
         governance: governance.clone(),
         slash_evidence: slash_evidence.clone(),
-    };
+        roles: roles.clone(),
+        issuers: issuers.clone(),
+        receipts: receipts.clone(),
+        nfts: nfts.clone(),
+        revoked_sessions: auth_manager.revoked_sessions(),
+    }
+}
 
-    save_state(&snapshot).map_err(|err| format!("Failed to save state: {}", err))
+/// Saves a snapshot to `path`, serializing and writing it off the async
+/// runtime once the locks are released.
+async fn flush_state(state: &AppState, path: &FsPath) -> Result<(), String> {
+    let snapshot = snapshot_state(state).await;
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || save_state(&path, &snapshot))
+        .await
+        .map_err(|err| format!("State writer panicked: {}", err))?
+        .map_err(|err| format!("Failed to save state: {}", err))
+}
+
+/// Writes the state to `path` whenever [`persist_state`] is called, coalescing
+/// requests that arrive during a write, and once more when `shutdown` fires.
+pub async fn run_persister(state: AppState, path: PathBuf, mut shutdown: oneshot::Receiver<()>) {
+    loop {
+        tokio::select! {
+            _ = state.persist.requested() => {}
+            _ = &mut shutdown => break,
+        }
+        if let Err(err) = flush_state(&state, &path).await {
+            eprintln!("⚠️  {}", err);
+        }
+    }
+    if let Err(err) = flush_state(&state, &path).await {
+        eprintln!("⚠️  {}", err);
+    }
 }
 
 async fn gossip_blocks(state: &AppState, blocks: Vec<Block>) -> Result<(), String> {
     let targets = {
-        let peers = state.peers.lock().await;
+        let peers = state.peers.read().await;
         peers.clone()
     };
 
@@ -236,13 +676,51 @@ async fn gossip_blocks(state: &AppState, blocks: Vec<Block>) -> Result<(), Strin
         failed += err;
     }
 
-    let mut metrics = state.metrics.lock().await;
+    let mut metrics = state.metrics.write().await;
     metrics.gossip_sent += sent;
     metrics.gossip_failed += failed;
 
     Ok(())
 }
 
+fn publish_block(state: &AppState, block: &Block) {
+    state.events.publish(ChainEvent::NewBlock {
+        index: block.index,
+        hash: block.hash.clone(),
+        validator: block.validator.clone(),
+        transaction_count: block.transactions.len(),
+    });
+}
+
+fn publish_finality(state: &AppState, previous_height: u64, finalized_height: u64) {
+    if finalized_height > previous_height {
+        state
+            .events
+            .publish(ChainEvent::FinalityAdvanced { finalized_height });
+    }
+}
+
+fn publish_slashes(state: &AppState, slashed: &[SlashEvent], reason: &str) {
+    for event in slashed {
+        state.events.publish(ChainEvent::Slashed {
+            address: event.address.clone(),
+            amount: event.amount,
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Stores a pending transaction's receipt and streams its logs.
+async fn record_receipt(state: &AppState, receipt: Receipt) {
+    for log in &receipt.logs {
+        state.events.publish(ChainEvent::Log {
+            transaction_id: receipt.transaction_id.clone(),
+            log: log.clone(),
+        });
+    }
+    state.receipts.write().await.record(receipt);
+}
+
 fn authorize_p2p(headers: &HeaderMap, state: &AppState) -> bool {
     let Some(token) = state.p2p_token.as_ref() else {
         return true;
@@ -253,32 +731,135 @@ fn authorize_p2p(headers: &HeaderMap, state: &AppState) -> bool {
         .is_some_and(|value| value == token)
 }
 
-fn authorize_admin(headers: &HeaderMap, state: &AppState) -> bool {
-    let Some(token) = state.admin_token.as_ref() else {
-        return true;
-    };
-    headers
-        .get("x-admin-token")
-        .and_then(|value| value.to_str().ok())
-        .is_some_and(|value| value == token)
+/// Restricts every route in `router` to sessions holding `role`.
+fn require_role(router: Router<AppState>, state: &AppState, role: Role) -> Router<AppState> {
+    router.route_layer(middleware::from_fn_with_state(
+        RoleGuard {
+            state: state.clone(),
+            role,
+        },
+        role_middleware,
+    ))
 }
 
-pub fn api_routes() -> Router<AppState> {
+/// Builds the API router. State-changing user actions sit behind
+/// `auth_middleware`, which needs the state up front to check sessions;
+/// privileged routes are grouped by the role they require.
+pub fn api_routes(state: AppState) -> Router<AppState> {
+    // Any authenticated wallet
+    let session_routes = Router::new()
+        .route("/tokens", post(create_token))
+        .route("/tokens/transfer", post(transfer_native_tokens))
+        .route("/tokens/{token}/transfer", post(transfer_tokens))
+        .route("/tokens/{token}/mint", post(mint_tokens))
+        .route("/tokens/{token}/burn", post(burn_tokens))
+        .route("/tokens/{token}/minters", post(set_token_minter))
+        .route("/tokens/{token}/approve", post(approve_tokens))
+        .route("/tokens/{token}/vesting", post(grant_vesting))
+        .route("/nfts", post(mint_nft))
+        .route("/nfts/{id}/transfer", post(transfer_nft))
+        .route("/nfts/{id}/burn", post(burn_nft))
+        .route("/tokens/{token}/transfer-from", post(transfer_tokens_from))
+        .route("/staking/withdraw", post(withdraw_stake))
+        .route("/contracts/deploy", post(deploy_contract))
+        .route("/contracts/call", post(call_contract))
+        .route(
+            "/certificates/rewards/claim",
+            post(claim_certificate_reward),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+    let issuer_routes = require_role(
+        Router::new()
+            .route("/certificates/issue", post(issue_certificate))
+            .route("/certificates/issue/batch", post(issue_certificate_batch))
+            .route("/certificates/suspend", post(suspend_certificate))
+            .route("/certificates/reinstate", post(reinstate_certificate))
+            .route("/certificates/revoke", post(revoke_certificate))
+            .route("/certificates/rewards/policy", post(set_reward_policy)),
+        &state,
+        Role::CertificateIssuer,
+    );
+    let validator_routes = require_role(
+        Router::new().route("/staking/deposit", post(stake_tokens)),
+        &state,
+        Role::ValidatorOperator,
+    );
+    let auditor_routes = require_role(
+        Router::new().route("/slashing/evidence", get(list_slash_evidence)),
+        &state,
+        Role::Auditor,
+    );
+    let admin_routes = require_role(
+        Router::new()
+            .route("/governance/config", post(update_governance))
+            .route("/governance/mint-authorities", post(set_mint_authority))
+            .route("/governance/max-supply", post(set_max_supply))
+            .route("/slashing/evidence", post(submit_slash_evidence))
+            .route("/rbac/grant", post(grant_role))
+            .route("/issuers/register", post(register_issuer))
+            .route("/issuers/remove", post(remove_issuer))
+            .route("/rbac/revoke", post(revoke_role)),
+        &state,
+        Role::Admin,
+    );
+
     Router::new()
+        .merge(session_routes)
+        .merge(issuer_routes)
+        .merge(validator_routes)
+        .merge(auditor_routes)
+        .merge(admin_routes)
         // Certificate routes
-        .route("/certificates/issue", post(issue_certificate))
+        .route("/certificates", get(list_certificates))
+        .route("/certificates/{id}", get(get_certificate))
+        .route(
+            "/accounts/{address}/certificates",
+            get(get_account_certificates),
+        )
         .route("/certificates/verify", post(verify_certificate))
+        .route("/certificates/revocations", get(get_revocation_list))
+        .route("/certificates/rewards/policies", get(list_reward_policies))
+        .route("/certificates/signing-payload", post(get_signing_payload))
+        .route("/certificates/{id}/credential", get(export_credential))
+        .route("/certificates/{id}/receipt", get(get_certificate_receipt))
+        .route(
+            "/certificates/batch/verify",
+            post(verify_certificate_receipt),
+        )
+        .route("/credentials/verify", post(verify_credential))
+        // Smart contract routes
+        .route("/contracts/{address}", get(get_contract))
+        // Event & receipt routes
+        .route("/events", get(query_events))
+        .route("/receipts/{transaction_id}", get(get_receipt))
+        .route("/blocks/{index}/receipts", get(get_block_receipts))
         // Token routes
-        .route("/tokens/transfer", post(transfer_tokens))
-        .route("/tokens/balance/{account}", get(get_token_balance))
+        .route("/tokens", get(list_tokens))
+        .route("/tokens/{token}", get(get_token))
+        .route("/tokens/balance/{account}", get(get_native_token_balance))
+        .route("/tokens/{token}/balance/{account}", get(get_token_balance))
+        .route(
+            "/tokens/{token}/allowance/{owner}/{spender}",
+            get(get_token_allowance),
+        )
+        .route("/tokens/{token}/vesting/{account}", get(get_vesting))
+        // NFT routes
+        .route("/nfts/{id}", get(get_nft))
+        .route("/accounts/{address}/nfts", get(get_account_nfts))
         // Blockchain routes
         .route("/blocks", get(get_blocks))
         .route("/blocks/{index}", get(get_block_by_index))
+        .route("/blocks/hash/{hash}", get(get_block_by_hash))
+        .route("/headers", get(get_headers))
         .route("/blockchain/stats", get(get_blockchain_stats))
         // Mining routes
         .route("/mine", post(mine_block))
         .route("/mining/difficulty", get(get_mining_difficulty))
-        .route("/mining/difficulty", post(set_mining_difficulty))
+        .route("/mining/jobs", get(list_mining_jobs))
+        .route("/mining/jobs/{id}", get(get_mining_job))
         // Validation routes
         .route("/blockchain/validate", get(validate_blockchain))
         .route("/blocks/{index}/validate", get(validate_block))
@@ -286,8 +867,6 @@ pub fn api_routes() -> Router<AppState> {
         // Transaction routes
         .route("/transactions/pending", get(get_pending_transactions))
         // Staking routes
-        .route("/staking/deposit", post(stake_tokens))
-        .route("/staking/withdraw", post(withdraw_stake))
         .route("/staking/validators", get(list_validators))
         // P2P routes
         .route("/p2p/peers", get(list_peers))
@@ -295,154 +874,1582 @@ pub fn api_routes() -> Router<AppState> {
         .route("/p2p/block", post(receive_block))
         .route("/p2p/blocks", post(receive_blocks))
         .route("/p2p/protocol", post(receive_protocol_message))
-        // Governance & slashing routes
+        // Governance & access control routes
         .route("/governance/config", get(get_governance))
-        .route("/governance/config", post(update_governance))
-        .route("/slashing/evidence", post(submit_slash_evidence))
-        .route("/slashing/evidence", get(list_slash_evidence))
+        .route("/rbac/roles/{address}", get(get_roles))
+        .route("/issuers", get(list_issuers))
         // Metrics
         .route("/metrics", get(get_metrics))
 }
 
 // ===== CERTIFICATE ENDPOINTS =====
 
+/// Whether `anchor` names a block on this chain containing its transaction,
+/// and that transaction was made for `reference` (a certificate id or batch
+/// root).
+fn is_anchored(chain: &Blockchain, anchor: &BlockAnchor, reference: &str) -> bool {
+    chain
+        .blocks
+        .get(anchor.block_index as usize)
+        .is_some_and(|block| {
+            block.hash == anchor.block_hash
+                && block
+                    .transactions
+                    .iter()
+                    .filter_map(BlockTransaction::as_transaction)
+                    .any(|tx| {
+                        tx.id == anchor.transaction_id && tx.reference.as_deref() == Some(reference)
+                    })
+        })
+}
+
+fn certificate_from_request(payload: &CertificateRequest, issuer: &str) -> Certificate {
+    let mut certificate = Certificate::new(
+        &payload.id,
+        &payload.issued_to,
+        &payload.description,
+        issuer,
+    );
+    certificate.credential_type = payload.credential_type.clone().unwrap_or_default();
+    certificate.expires_at = payload.expires_at;
+    certificate.metadata = payload.metadata.clone();
+    certificate.content_hash = payload
+        .content_hash
+        .as_ref()
+        .map(|hash| hash.to_lowercase());
+    certificate.signature = payload.signature.clone();
+    certificate
+}
+
+async fn get_certificate(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Json<Option<Certificate>> {
+    let contracts = state.contracts.read().await;
+    Json(contracts.certificates.get(&id).cloned())
+}
+
+async fn list_certificates(
+    State(state): State<AppState>,
+    Query(query): Query<CertificateListQuery>,
+) -> Json<CertificatePage> {
+    let contracts = state.contracts.read().await;
+    let filter = CertificateQuery {
+        holder: query.holder,
+        issuer: query.issuer,
+        status: query.status,
+    };
+    let matches = contracts.query_certificates(&filter, Utc::now());
+    let (from, limit) = page_bounds(query.from, query.limit);
+    Json(CertificatePage {
+        total: matches.len(),
+        from,
+        limit,
+        certificates: matches
+            .into_iter()
+            .skip(from)
+            .take(limit)
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Certificates held by an account; same filters as `/certificates` except `holder`.
+async fn get_account_certificates(
+    state: State<AppState>,
+    Path(address): Path<String>,
+    Query(query): Query<CertificateListQuery>,
+) -> Json<CertificatePage> {
+    let query = CertificateListQuery {
+        holder: Some(address),
+        ..query
+    };
+    list_certificates(state, Query(query)).await
+}
+
+/// Returns the canonical payload an issuer signs for a certificate request.
+/// Takes the same body as `/certificates/issue`; `issuer` is required here.
+async fn get_signing_payload(
+    Json(payload): Json<CertificateRequest>,
+) -> ApiResult<SigningPayloadResponse> {
+    let issuer = payload
+        .issuer
+        .as_deref()
+        .ok_or_else(|| ApiError::bad_request("missing_field", "issuer is required"))?;
+    let certificate = certificate_from_request(&payload, issuer);
+    Ok(Json(SigningPayloadResponse {
+        status: "success".to_string(),
+        message: "Sign this payload with the issuer key".to_string(),
+        payload: Some(certificate.signing_payload()),
+    }))
+}
+
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`).
 async fn issue_certificate(
     State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
     Json(payload): Json<CertificateRequest>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
+    let issuer = payload.issuer.clone().unwrap_or_else(|| session.0.clone());
+    if !session.matches(&issuer) {
+        return Err(ApiError::session_mismatch("issuer"));
+    }
+
+    let certificate = certificate_from_request(&payload, &issuer);
+    if certificate.signature.is_some() {
+        let check = {
+            let issuers = state.issuers.read().await;
+            certificate.check_signature(&issuers)
+        };
+        if !check.signature_valid {
+            return Err(ApiError::bad_request(
+                "invalid_issuer_signature",
+                check.message,
+            ));
+        }
+    }
+
     // Update contract state
-    let mut contracts = state.contracts.lock().await;
-    contracts.issue_certificate(&payload.id, &payload.issued_to, &payload.description);
+    let mut contracts = state.contracts.write().await;
+    let issued = contracts.issue_certificate(certificate);
+    let mut logs = contracts.take_logs();
+    if issued.is_ok() && payload.mint_nft {
+        logs.extend(mint_certificate_nfts(&state, &contracts, [payload.id.as_str()]).await);
+    }
     drop(contracts);
+    issued?;
 
     // Create blockchain transaction
     let transaction = Transaction::new(
-        None, // No sender for certificate issuance
+        Some(issuer.clone()),
         payload.issued_to.clone(),
         0, // Certificates don't transfer tokens
         TransactionType::Certificate,
-    );
+    )
+    .with_reference(&payload.id);
 
     // Add to pending transactions
-    let mut pending = state.pending_transactions.lock().await;
-    pending.push(transaction);
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
     drop(pending);
+    record_receipt(&state, Receipt::new(&transaction, logs)).await;
 
-    let _ = persist_state(&state).await;
+    persist_state(&state);
 
-    Json(ApiResponse {
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(ApiResponse {
         status: "success".to_string(),
         message: format!(
             "Certificate {} issued to {} and added to pending transactions",
             payload.id, payload.issued_to
         ),
-    })
+    }))
 }
 
-async fn verify_certificate(
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`). Issues every
+/// certificate under one Merkle root, anchored by a single transaction.
+async fn issue_certificate_batch(
     State(state): State<AppState>,
-    Json(payload): Json<VerifyCertificateRequest>,
-) -> Json<ApiResponse> {
-    let mut contracts = state.contracts.lock().await;
-    let success = contracts.verify_certificate(&payload.id);
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<BatchCertificateRequest>,
+) -> ApiResult<BatchIssueResponse> {
+    if payload.certificates.len() > MAX_CERTIFICATE_BATCH_SIZE {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "batch_too_large",
+            format!(
+                "A batch holds at most {} certificates",
+                MAX_CERTIFICATE_BATCH_SIZE
+            ),
+        ));
+    }
 
-    let _ = persist_state(&state).await;
+    let mut certificates = Vec::with_capacity(payload.certificates.len());
+    for request in &payload.certificates {
+        let issuer = request.issuer.clone().unwrap_or_else(|| session.0.clone());
+        if !session.matches(&issuer) {
+            return Err(ApiError::session_mismatch("issuer"));
+        }
+        certificates.push(certificate_from_request(request, &issuer));
+    }
 
-    Json(ApiResponse {
-        status: if success { "success" } else { "error" }.to_string(),
-        message: if success {
-            format!("Certificate {} verified", payload.id)
-        } else {
-            format!("Failed to verify certificate {}", payload.id)
-        },
-    })
+    {
+        let issuers = state.issuers.read().await;
+        for certificate in certificates.iter().filter(|cert| cert.signature.is_some()) {
+            let check = certificate.check_signature(&issuers);
+            if !check.signature_valid {
+                return Err(ApiError::bad_request(
+                    "invalid_issuer_signature",
+                    format!("Certificate {}: {}", certificate.id, check.message),
+                ));
+            }
+        }
+    }
+
+    let mut contracts = state.contracts.write().await;
+    let batch = contracts.issue_batch(certificates, &session.0)?;
+    let receipts: Vec<CertificateReceipt> = batch
+        .certificate_ids
+        .iter()
+        .filter_map(|id| contracts.certificates.get(id))
+        .filter_map(Certificate::receipt)
+        .collect();
+    let mut logs = contracts.take_logs();
+    let nft_ids = payload
+        .certificates
+        .iter()
+        .filter(|request| request.mint_nft)
+        .map(|request| request.id.as_str());
+    logs.extend(mint_certificate_nfts(&state, &contracts, nft_ids).await);
+    drop(contracts);
+
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        session.0.clone(),
+        0,
+        TransactionType::CertificateBatch,
+    )
+    .with_reference(&batch.root);
+
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
+    drop(pending);
+    record_receipt(&state, Receipt::new(&transaction, logs)).await;
+
+    persist_state(&state);
+
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(BatchIssueResponse {
+        status: "success".to_string(),
+        message: format!(
+            "Issued {} certificates under Merkle root {}",
+            receipts.len(),
+            batch.root
+        ),
+        merkle_root: Some(batch.root),
+        receipts,
+    }))
+}
+
+/// Mints a soulbound NFT for each issued certificate in `ids`. Called with the
+/// contracts lock held, which comes before the NFT lock (see [`AppState`]).
+async fn mint_certificate_nfts<'a>(
+    state: &AppState,
+    contracts: &ContractExecutor,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> Vec<EventLog> {
+    let mut nfts = state.nfts.write().await;
+    ids.into_iter()
+        .filter_map(|id| contracts.certificates.get(id))
+        .map(|certificate| nfts.mint_for_certificate(certificate).minted_log())
+        .collect()
+}
+
+async fn get_certificate_receipt(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<ReceiptResponse> {
+    let contracts = state.contracts.read().await;
+    let receipt = contracts
+        .certificates
+        .get(&id)
+        .ok_or_else(|| CertificateError::NotFound(id.clone()))?
+        .receipt();
+    drop(contracts);
+
+    let receipt = receipt.ok_or_else(|| {
+        ApiError::not_found(
+            "receipt_not_found",
+            format!("Certificate {} was not batch-issued", id),
+        )
+    })?;
+    Ok(Json(ReceiptResponse {
+        status: "success".to_string(),
+        message: format!("Inclusion proof for certificate {}", id),
+        receipt: Some(receipt),
+    }))
+}
+
+/// Verifies a holder's receipt from the proof and the anchored root alone,
+/// then reports the certificate's current status if this node knows it.
+async fn verify_certificate_receipt(
+    State(state): State<AppState>,
+    Json(receipt): Json<CertificateReceipt>,
+) -> Json<ReceiptVerificationResponse> {
+    let proof_valid = receipt.proof_is_valid();
+
+    let root = receipt.merkle_root.to_lowercase();
+    let contracts = state.contracts.read().await;
+    let anchor = contracts
+        .batches
+        .get(&root)
+        .and_then(|batch| batch.anchor.clone());
+    // Only trust the status of the certificate the payload actually describes
+    let certificate_status = serde_json::from_str::<serde_json::Value>(&receipt.payload)
+        .ok()
+        .and_then(|payload| payload["id"].as_str().map(str::to_string))
+        .filter(|id| *id == receipt.certificate_id)
+        .and_then(|id| contracts.certificates.get(&id))
+        .map(|cert| cert.effective_status(Utc::now()));
+    drop(contracts);
+
+    let root_anchored = match anchor {
+        Some(anchor) => is_anchored(&*state.chain.read().await, &anchor, &root),
+        None => false,
+    };
+
+    let success = proof_valid
+        && root_anchored
+        && certificate_status.is_none_or(|status| status == CertificateStatus::Active);
+    let message = if success {
+        format!(
+            "Certificate {} is included in an anchored batch",
+            receipt.certificate_id
+        )
+    } else if !proof_valid {
+        "Inclusion proof does not match the payload and root".to_string()
+    } else if !root_anchored {
+        "Merkle root is not anchored on this chain".to_string()
+    } else {
+        format!(
+            "Certificate is {:?}",
+            certificate_status.unwrap_or_default()
+        )
+        .to_lowercase()
+    };
+
+    Json(ReceiptVerificationResponse {
+        status: if success { "success" } else { "error" }.to_string(),
+        message,
+        proof_valid,
+        root_anchored,
+        certificate_status,
+    })
+}
+
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`).
+async fn suspend_certificate(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<CertificateStatusRequest>,
+) -> ApiResult<ApiResponse> {
+    change_certificate_status(state, session, payload, CertificateStatus::Suspended).await
+}
+
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`).
+async fn reinstate_certificate(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<CertificateStatusRequest>,
+) -> ApiResult<ApiResponse> {
+    change_certificate_status(state, session, payload, CertificateStatus::Active).await
+}
+
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`).
+async fn revoke_certificate(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<CertificateStatusRequest>,
+) -> ApiResult<ApiResponse> {
+    change_certificate_status(state, session, payload, CertificateStatus::Revoked).await
+}
+
+/// Records a status change as a pending `CertificateStatus` transaction. Only
+/// the certificate's issuer or an admin may change its status.
+async fn change_certificate_status(
+    state: AppState,
+    session: AuthenticatedAddress,
+    payload: CertificateStatusRequest,
+    status: CertificateStatus,
+) -> ApiResult<ApiResponse> {
+    let is_admin = state.roles.read().await.has_role(&session.0, Role::Admin);
+
+    let mut contracts = state.contracts.write().await;
+    let cert = contracts
+        .certificates
+        .get(&payload.id)
+        .ok_or_else(|| CertificateError::NotFound(payload.id.clone()))?;
+    if !is_admin && !session.matches(&cert.issuer) {
+        return Err(ApiError::forbidden(
+            "not_certificate_issuer",
+            "Only the issuer or an admin can change a certificate's status",
+        ));
+    }
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        cert.issued_to.clone(),
+        0,
+        TransactionType::CertificateStatus,
+    )
+    .with_reference(&payload.id);
+    let (id, reason, note) = (&payload.id, payload.reason, payload.note);
+    let changed = match status {
+        CertificateStatus::Revoked => {
+            contracts.revoke_certificate(id, reason, note, &session.0, &transaction.id)
+        }
+        CertificateStatus::Suspended => {
+            contracts.suspend_certificate(id, reason, note, &session.0, &transaction.id)
+        }
+        _ => contracts.change_status(id, status, reason, note, &session.0, &transaction.id),
+    };
+    let logs = contracts.take_logs();
+    drop(contracts);
+    changed?;
+
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
+    drop(pending);
+    record_receipt(&state, Receipt::new(&transaction, logs)).await;
+
+    persist_state(&state);
+
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!(
+            "Certificate {} is now {:?} and the change was added to pending transactions",
+            payload.id, status
+        ),
+    }))
+}
+
+/// Revoked and suspended certificates. Verifiers may cache the list for a
+/// minute; `?issuer=` narrows it to one issuer.
+async fn get_revocation_list(
+    State(state): State<AppState>,
+    Query(filter): Query<IssuerFilterQuery>,
+) -> impl IntoResponse {
+    let contracts = state.contracts.read().await;
+    let entries = contracts
+        .revoked_or_suspended()
+        .into_iter()
+        .filter(|cert| {
+            filter
+                .issuer
+                .as_ref()
+                .is_none_or(|issuer| cert.issuer.eq_ignore_ascii_case(issuer))
+        })
+        .map(|cert| {
+            let last_change = cert.status_history.last();
+            RevocationEntry {
+                id: cert.id.clone(),
+                issuer: cert.issuer.clone(),
+                status: cert.status,
+                reason: last_change.map(|change| change.reason),
+                changed_at: last_change.map(|change| change.changed_at),
+                anchor: last_change.and_then(|change| change.anchor.clone()),
+            }
+        })
+        .collect();
+    drop(contracts);
+
+    (
+        [(header::CACHE_CONTROL, REVOCATION_LIST_CACHE_CONTROL)],
+        Json(RevocationListResponse {
+            generated_at: Utc::now(),
+            entries,
+        }),
+    )
+}
+
+/// Verifies a certificate. Verification changes no state; holders claim
+/// rewards through `claim_certificate_reward`.
+async fn verify_certificate(
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyCertificateRequest>,
+) -> Json<CertificateVerificationResponse> {
+    let contracts = state.contracts.read().await;
+    let issuers = state.issuers.read().await;
+    let verified = contracts.verify_certificate(&payload.id, &issuers);
+    let certificate = contracts.certificates.get(&payload.id);
+    let signature = certificate.map(|cert| cert.check_signature(&issuers));
+    let issued_to = certificate.map(|cert| cert.issued_to.clone());
+    let certificate_status = certificate.map(|cert| cert.effective_status(Utc::now()));
+    let status_history = certificate
+        .map(|cert| cert.status_history.clone())
+        .unwrap_or_default();
+    let reward = certificate.and_then(|cert| cert.reward.clone());
+    drop(issuers);
+    drop(contracts);
+
+    let success = verified.is_ok();
+    let message = match verified {
+        Ok(()) => {
+            let message = format!("Certificate {} verified", payload.id);
+            if let Some(issued_to) = issued_to {
+                state.events.publish(ChainEvent::CertificateVerified {
+                    id: payload.id.clone(),
+                    issued_to,
+                });
+            }
+            message
+        }
+        Err(err) => {
+            let message = err.to_string();
+            state
+                .events
+                .publish(ChainEvent::CertificateVerificationFailed {
+                    id: payload.id.clone(),
+                    issued_to,
+                    reason: message.clone(),
+                });
+            message
+        }
+    };
+
+    Json(CertificateVerificationResponse {
+        status: if success { "success" } else { "error" }.to_string(),
+        message,
+        certificate_status,
+        status_history,
+        signature,
+        reward,
+    })
+}
+
+/// Pays the session holder's reward for a certificate that verifies, under
+/// its issuer's reward policy. Each certificate is paid at most once, so
+/// repeated claims return the existing reward.
+async fn claim_certificate_reward(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<RewardClaimRequest>,
+) -> ApiResult<RewardClaimResponse> {
+    // Lock order: see `AppState`
+    let mut tokens = state.tokens.write().await;
+    let mut contracts = state.contracts.write().await;
+    let issuers = state.issuers.read().await;
+    let cert = contracts
+        .certificates
+        .get(&payload.id)
+        .ok_or_else(|| CertificateError::NotFound(payload.id.clone()))?;
+    if !session.matches(&cert.issued_to) {
+        return Err(ApiError::forbidden(
+            "not_certificate_holder",
+            "Only the certificate's holder can claim its reward",
+        ));
+    }
+    if let Some(reward) = cert.reward.clone() {
+        return Ok(Json(RewardClaimResponse {
+            status: "info".to_string(),
+            message: format!("Certificate {} was already rewarded", payload.id),
+            reward: Some(reward),
+        }));
+    }
+    contracts.verify_certificate(&payload.id, &issuers)?;
+    drop(issuers);
+    let rewarded = contracts.reward_certificate_holder(tokens.native_mut(), &payload.id)?;
+    let reward = contracts
+        .certificates
+        .get(&payload.id)
+        .and_then(|cert| cert.reward.clone());
+    let logs = contracts.take_logs();
+    drop(contracts);
+    drop(tokens);
+
+    let Some(transaction) = rewarded else {
+        return Ok(Json(RewardClaimResponse {
+            status: "info".to_string(),
+            message: format!("No reward policy covers certificate {}", payload.id),
+            reward: None,
+        }));
+    };
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
+    drop(pending);
+    record_receipt(&state, Receipt::new(&transaction, logs)).await;
+    persist_state(&state);
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(RewardClaimResponse {
+        status: "success".to_string(),
+        message: format!("Holder of certificate {} rewarded", payload.id),
+        reward,
+    }))
+}
+
+/// Requires [`Role::CertificateIssuer`] (see `api_routes`). Sets the session
+/// issuer's reward for one credential type and escrows its budget from the
+/// issuer's native balance.
+async fn set_reward_policy(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<RewardPolicyRequest>,
+) -> ApiResult<ApiResponse> {
+    // Lock order: see `AppState`
+    let mut tokens = state.tokens.write().await;
+    let mut contracts = state.contracts.write().await;
+    let updated = contracts.set_reward_policy(
+        tokens.native_mut(),
+        RewardPolicy {
+            issuer: session.0.clone(),
+            credential_type: payload.credential_type.clone(),
+            amount: payload.amount,
+            budget: payload.budget,
+        },
+    );
+    drop(contracts);
+    drop(tokens);
+    updated?;
+
+    persist_state(&state);
+    state.events.publish(ChainEvent::RewardPolicyUpdated {
+        issuer: session.0.clone(),
+        credential_type: payload.credential_type.clone(),
+        amount: payload.amount,
+        budget: payload.budget,
+    });
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!(
+            "Holders of {} certificates from {} will receive {} tokens",
+            payload.credential_type, session.0, payload.amount
+        ),
+    }))
+}
+
+async fn list_reward_policies(
+    State(state): State<AppState>,
+    Query(filter): Query<IssuerFilterQuery>,
+) -> Json<Vec<RewardPolicy>> {
+    let contracts = state.contracts.read().await;
+    Json(
+        contracts
+            .list_reward_policies()
+            .into_iter()
+            .filter(|policy| {
+                filter
+                    .issuer
+                    .as_ref()
+                    .is_none_or(|issuer| policy.issuer.eq_ignore_ascii_case(issuer))
+            })
+            .cloned()
+            .collect(),
+    )
+}
+
+// ===== VERIFIABLE CREDENTIAL ENDPOINTS =====
+
+async fn export_credential(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<CredentialExportResponse> {
+    let contracts = state.contracts.read().await;
+    let cert = contracts
+        .certificates
+        .get(&id)
+        .ok_or_else(|| CertificateError::NotFound(id.clone()))?;
+    let credential = VerifiableCredential::from_certificate(cert)
+        .map_err(|message| ApiError::conflict("credential_unavailable", message))?;
+    drop(contracts);
+
+    Ok(Json(CredentialExportResponse {
+        status: "success".to_string(),
+        message: format!("Certificate {} exported as a verifiable credential", id),
+        credential: Some(credential),
+    }))
+}
+
+/// Verifies a credential presented by a holder: the issuer signature, the
+/// on-chain anchor, and that it matches this chain's record and status.
+/// A credential that fails a check is still answered with 200.
+async fn verify_credential(
+    State(state): State<AppState>,
+    Json(credential): Json<VerifiableCredential>,
+) -> ApiResult<CredentialVerificationResponse> {
+    let presented = credential
+        .to_certificate()
+        .map_err(|message| ApiError::bad_request("invalid_credential", message))?;
+
+    let contracts = state.contracts.read().await;
+    let issuers = state.issuers.read().await;
+    let signature = presented.check_signature(&issuers);
+    let record = contracts.certificates.get(&presented.id);
+    let matches_record = record.is_some_and(|record| {
+        record.signing_payload() == presented.signing_payload()
+            && record.signature == presented.signature
+            && record.anchor == presented.anchor
+    });
+    let certificate_status = record.map(|record| record.effective_status(Utc::now()));
+    // Batch-issued certificates are anchored by their batch's root
+    let anchor_reference = record
+        .and_then(|record| record.batch.as_ref())
+        .map_or_else(|| presented.id.clone(), |batch| batch.root.clone());
+    drop(issuers);
+    drop(contracts);
+
+    let anchored = match &presented.anchor {
+        Some(anchor) => is_anchored(&*state.chain.read().await, anchor, &anchor_reference),
+        None => false,
+    };
+
+    let success = signature.is_valid()
+        && anchored
+        && matches_record
+        && certificate_status == Some(CertificateStatus::Active);
+    let message = if success {
+        format!("Credential for certificate {} verified", presented.id)
+    } else if !signature.is_valid() {
+        signature.message.clone()
+    } else if !anchored {
+        "Credential anchor does not match this chain".to_string()
+    } else if !matches_record {
+        "Credential does not match the on-chain certificate".to_string()
+    } else {
+        format!(
+            "Certificate is {:?}",
+            certificate_status.unwrap_or_default()
+        )
+        .to_lowercase()
+    };
+
+    Ok(Json(CredentialVerificationResponse {
+        status: if success { "success" } else { "error" }.to_string(),
+        message,
+        certificate_id: Some(presented.id),
+        signature: Some(signature),
+        anchored,
+        matches_record,
+        certificate_status,
+    }))
+}
+
+async fn list_issuers(State(state): State<AppState>) -> Json<Vec<TrustedIssuer>> {
+    let issuers = state.issuers.read().await;
+    Json(issuers.list().into_iter().cloned().collect())
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
+async fn register_issuer(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<IssuerRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut issuers = state.issuers.write().await;
+    let registered = issuers.register(TrustedIssuer {
+        address: payload.address.clone(),
+        public_key: payload.public_key.map(|key| key.to_lowercase()),
+        name: payload.name,
+        added_by: session.0,
+        added_at: Utc::now(),
+    });
+    drop(issuers);
+    registered.map_err(|message| ApiError::bad_request("invalid_issuer", message))?;
+
+    persist_state(&state);
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!("{} is now a trusted issuer", payload.address),
+    }))
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
+async fn remove_issuer(
+    State(state): State<AppState>,
+    Json(payload): Json<RemoveIssuerRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut issuers = state.issuers.write().await;
+    let removed = issuers.remove(&payload.address);
+    drop(issuers);
+
+    if !removed {
+        return Err(ApiError::not_found(
+            "issuer_not_found",
+            format!("{} is not a trusted issuer", payload.address),
+        ));
+    }
+
+    persist_state(&state);
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!("{} is no longer a trusted issuer", payload.address),
+    }))
 }
 
 // ===== TOKEN ENDPOINTS =====
 
+/// Adds a token transaction to pending transactions with its receipt.
+async fn record_token_transaction(
+    state: &AppState,
+    transaction: Transaction,
+    logs: Vec<EventLog>,
+    message: String,
+) -> ApiResult<ApiResponse> {
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
+    drop(pending);
+    record_receipt(state, Receipt::new(&transaction, logs)).await;
+
+    persist_state(state);
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message,
+    }))
+}
+
+async fn list_tokens(State(state): State<AppState>) -> Json<Vec<RegisteredToken>> {
+    let tokens = state.tokens.read().await;
+    Json(tokens.list().into_iter().cloned().collect())
+}
+
+async fn get_token(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Json<Option<RegisteredToken>> {
+    let tokens = state.tokens.read().await;
+    Json(tokens.get(&token).cloned())
+}
+
+/// Requires an authenticated session (see `api_routes`). The session address
+/// owns the new token and receives its initial supply.
+async fn create_token(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<TokenCreateRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let created = tokens
+        .create(
+            &payload.name,
+            &payload.symbol,
+            payload.initial_supply,
+            payload.max_supply,
+            &session.0,
+            payload.transferable,
+        )
+        .map(|entry| entry.token.symbol.clone());
+    drop(tokens);
+    let symbol = created?;
+
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        session.0.clone(),
+        payload.initial_supply,
+        TransactionType::TokenCreate,
+    )
+    .with_reference(&symbol);
+    let logs = vec![EventLog::new(TOKEN_CONTRACT, "TokenCreated")
+        .with("token", &symbol)
+        .with("owner", &session.0)
+        .with("amount", payload.initial_supply)];
+    let message = format!("Created token {} owned by {}", symbol, session.0);
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+/// Requires an authenticated session that is one of the token's mint authorities.
+async fn mint_tokens(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<TokenMintRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let minted = tokens.mint(&token, &session.0, &payload.to, payload.amount);
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    minted?;
+
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        payload.to.clone(),
+        payload.amount,
+        TransactionType::TokenMint,
+    )
+    .with_reference(&symbol);
+    let logs = vec![EventLog::new(TOKEN_CONTRACT, "Mint")
+        .with("token", &symbol)
+        .with("to", &payload.to)
+        .with("amount", payload.amount)];
+    let message = format!("Minted {} {} to {}", payload.amount, symbol, payload.to);
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+/// Requires an authenticated session matching `from`.
+async fn burn_tokens(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<TokenBurnRequest>,
+) -> ApiResult<ApiResponse> {
+    if !session.matches(&payload.from) {
+        return Err(ApiError::session_mismatch("from"));
+    }
+
+    let mut tokens = state.tokens.write().await;
+    let burned = tokens.burn(&token, &payload.from, payload.amount);
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    burned?;
+
+    let transaction = Transaction::new(
+        Some(payload.from.clone()),
+        String::new(),
+        payload.amount,
+        TransactionType::TokenBurn,
+    )
+    .with_reference(&symbol);
+    let logs = vec![EventLog::new(TOKEN_CONTRACT, "Burn")
+        .with("token", &symbol)
+        .with("from", &payload.from)
+        .with("amount", payload.amount)];
+    let message = format!("Burned {} {} from {}", payload.amount, symbol, payload.from);
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+/// Requires an authenticated session that owns the token.
+async fn set_token_minter(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<TokenMinterRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let updated = tokens.set_minter(&token, &session.0, &payload.account, payload.allowed);
+    drop(tokens);
+    updated?;
+
+    persist_state(&state);
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!(
+            "{} {} mint {}",
+            payload.account,
+            if payload.allowed {
+                "may now"
+            } else {
+                "may no longer"
+            },
+            token.to_uppercase()
+        ),
+    }))
+}
+
+/// Transfers the native token; kept for clients that predate the registry.
+async fn transfer_native_tokens(
+    state: State<AppState>,
+    session: Extension<AuthenticatedAddress>,
+    payload: Json<TokenTransferRequest>,
+) -> ApiResult<ApiResponse> {
+    transfer_tokens(state, session, Path(NATIVE_TOKEN.to_string()), payload).await
+}
+
+/// Requires an authenticated session (see `api_routes`).
 async fn transfer_tokens(
     State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
     Json(payload): Json<TokenTransferRequest>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
+    if !session.matches(&payload.from) {
+        return Err(ApiError::session_mismatch("from"));
+    }
+
     // Update token balances
-    let mut token = state.token.lock().await;
-    let success = token.transfer(&payload.from, &payload.to, payload.amount);
-    drop(token);
-
-    if success {
-        // Create blockchain transaction
-        let transaction = Transaction::new(
-            Some(payload.from.clone()),
-            payload.to.clone(),
-            payload.amount,
-            TransactionType::Transfer,
-        );
+    let mut tokens = state.tokens.write().await;
+    let transferred = tokens.transfer(&token, &payload.from, &payload.to, payload.amount);
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    transferred?;
 
-        // Add to pending transactions
-        let mut pending = state.pending_transactions.lock().await;
-        pending.push(transaction);
-        drop(pending);
+    // Create blockchain transaction
+    let transaction = Transaction::new(
+        Some(payload.from.clone()),
+        payload.to.clone(),
+        payload.amount,
+        TransactionType::Transfer,
+    )
+    .with_reference(&symbol);
+    let logs = vec![EventLog::transfer(
+        &symbol,
+        &payload.from,
+        &payload.to,
+        payload.amount,
+    )];
+    let message = format!(
+        "Transferred {} {} from {} to {} and added to blockchain",
+        payload.amount, symbol, payload.from, payload.to
+    );
+    record_token_transaction(&state, transaction, logs, message).await
+}
 
-        let _ = persist_state(&state).await;
+/// Requires an authenticated session matching `owner`.
+async fn approve_tokens(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<TokenApproveRequest>,
+) -> ApiResult<ApiResponse> {
+    if !session.matches(&payload.owner) {
+        return Err(ApiError::session_mismatch("owner"));
+    }
 
-        Json(ApiResponse {
-            status: "success".to_string(),
-            message: format!(
-                "Transferred {} tokens from {} to {} and added to blockchain",
-                payload.amount, payload.from, payload.to
-            ),
-        })
-    } else {
-        Json(ApiResponse {
-            status: "error".to_string(),
-            message: format!(
-                "Failed to transfer tokens from {} to {}",
-                payload.from, payload.to
-            ),
+    let mut tokens = state.tokens.write().await;
+    let approved = tokens.approve(&token, &payload.owner, &payload.spender, payload.amount);
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    approved?;
+
+    let transaction = Transaction::new(
+        Some(payload.owner.clone()),
+        payload.spender.clone(),
+        payload.amount,
+        TransactionType::TokenApprove,
+    )
+    .with_reference(&symbol);
+    let logs = vec![EventLog::approval(
+        &symbol,
+        &payload.owner,
+        &payload.spender,
+        payload.amount,
+    )];
+    let message = format!(
+        "{} may spend {} {} from {}",
+        payload.spender, payload.amount, symbol, payload.owner
+    );
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+/// Requires an authenticated session; the session address is the spender
+/// whose allowance from `owner` pays for the transfer.
+async fn transfer_tokens_from(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<TokenTransferFromRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let transferred = tokens.transfer_from(
+        &token,
+        &session.0,
+        &payload.owner,
+        &payload.to,
+        payload.amount,
+    );
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    let remaining = transferred?;
+
+    let transaction = Transaction::new(
+        Some(payload.owner.clone()),
+        payload.to.clone(),
+        payload.amount,
+        TransactionType::Transfer,
+    )
+    .with_reference(&symbol);
+    let logs = vec![
+        EventLog::transfer(&symbol, &payload.owner, &payload.to, payload.amount)
+            .with("spender", &session.0),
+        EventLog::approval(&symbol, &payload.owner, &session.0, remaining),
+    ];
+    let message = format!(
+        "{} transferred {} {} from {} to {}",
+        session.0, payload.amount, symbol, payload.owner, payload.to
+    );
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+async fn get_token_allowance(
+    State(state): State<AppState>,
+    Path((token, owner, spender)): Path<(String, String, String)>,
+) -> Json<Option<AllowanceResponse>> {
+    let tokens = state.tokens.read().await;
+    Json(tokens.get(&token).map(|entry| AllowanceResponse {
+        token: entry.token.symbol.clone(),
+        allowance: entry.token.allowance(&owner, &spender),
+        owner,
+        spender,
+    }))
+}
+
+/// Requires an authenticated session; the session address funds the grant.
+async fn grant_vesting(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(token): Path<String>,
+    Json(payload): Json<VestingGrantRequest>,
+) -> ApiResult<ApiResponse> {
+    let schedule = VestingSchedule {
+        grantor: session.0.clone(),
+        amount: payload.amount,
+        start: payload.start.unwrap_or_else(Utc::now),
+        cliff_seconds: payload.cliff_seconds,
+        duration_seconds: payload.duration_seconds,
+    };
+    let mut tokens = state.tokens.write().await;
+    let granted = tokens.grant_vesting(&token, &payload.beneficiary, schedule.clone());
+    let symbol = tokens.symbol(&token);
+    drop(tokens);
+    granted?;
+
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        payload.beneficiary.clone(),
+        payload.amount,
+        TransactionType::VestingGrant,
+    )
+    .with_reference(&symbol);
+    let logs = vec![
+        EventLog::transfer(&symbol, &session.0, &payload.beneficiary, payload.amount),
+        EventLog::new(TOKEN_CONTRACT, "VestingGranted")
+            .with("token", &symbol)
+            .with("from", &session.0)
+            .with("to", &payload.beneficiary)
+            .with("amount", payload.amount)
+            .with("start", schedule.start.to_rfc3339())
+            .with("cliff_seconds", schedule.cliff_seconds)
+            .with("duration_seconds", schedule.duration_seconds),
+    ];
+    let message = format!(
+        "Granted {} {} to {} vesting until {}",
+        payload.amount,
+        symbol,
+        payload.beneficiary,
+        schedule.end().to_rfc3339()
+    );
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+async fn get_vesting(
+    State(state): State<AppState>,
+    Path((token, account)): Path<(String, String)>,
+) -> Json<Option<VestingResponse>> {
+    let tokens = state.tokens.read().await;
+    let Some(entry) = tokens.get(&token) else {
+        return Json(None);
+    };
+    let now = Utc::now();
+    let schedules: Vec<VestingScheduleStatus> = entry
+        .token
+        .vesting
+        .get(&account)
+        .into_iter()
+        .flatten()
+        .map(|schedule| VestingScheduleStatus {
+            ends_at: schedule.end(),
+            vested: schedule.vested_at(now),
+            unvested: schedule.locked_at(now),
+            schedule: schedule.clone(),
         })
-    }
+        .collect();
+    Json(Some(VestingResponse {
+        token: entry.token.symbol.clone(),
+        balance: entry.token.balance_of(&account),
+        available: entry.token.available_at(&account, now),
+        vested: schedules.iter().map(|status| status.vested).sum(),
+        unvested: schedules.iter().map(|status| status.unvested).sum(),
+        schedules,
+        account,
+    }))
 }
 
-async fn get_token_balance(
+async fn get_native_token_balance(
     State(state): State<AppState>,
     Path(account): Path<String>,
 ) -> Json<BalanceResponse> {
-    let token = state.token.lock().await;
-    let balance = token.balance_of(&account);
+    let tokens = state.tokens.read().await;
+    let native = tokens.native();
+    Json(BalanceResponse {
+        token: native.symbol.clone(),
+        balance: native.balance_of(&account),
+        account,
+    })
+}
+
+async fn get_token_balance(
+    State(state): State<AppState>,
+    Path((token, account)): Path<(String, String)>,
+) -> Json<Option<BalanceResponse>> {
+    let tokens = state.tokens.read().await;
+    Json(tokens.get(&token).map(|entry| BalanceResponse {
+        token: entry.token.symbol.clone(),
+        balance: entry.token.balance_of(&account),
+        account,
+    }))
+}
+
+// ===== NFT ENDPOINTS =====
+
+/// Requires an authenticated session, which is recorded as the minter.
+/// Minting to another account also requires [`Role::CertificateIssuer`], so
+/// nobody can be handed a soulbound NFT they never asked for.
+async fn mint_nft(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<NftMintRequest>,
+) -> ApiResult<ApiResponse> {
+    if !session.matches(&payload.to) {
+        let roles = state.roles.read().await;
+        if !roles.has_role(&session.0, Role::CertificateIssuer) {
+            return Err(ApiError::forbidden(
+                "nft_mint_forbidden",
+                "Only certificate issuers and admins can mint NFTs to other accounts",
+            ));
+        }
+    }
+
+    let mut nfts = state.nfts.write().await;
+    let minted = nfts
+        .mint(
+            &session.0,
+            &payload.to,
+            &payload.metadata_uri,
+            payload.soulbound,
+        )
+        .map(|nft| (nft.id.clone(), nft.minted_log()));
+    drop(nfts);
+    let (id, log) = minted?;
 
-    Json(BalanceResponse { account, balance })
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        payload.to.clone(),
+        0,
+        TransactionType::NftMint,
+    )
+    .with_reference(&id);
+    let message = format!("Minted NFT {} to {}", id, payload.to);
+    record_token_transaction(&state, transaction, vec![log], message).await
+}
+
+/// Requires an authenticated session matching `from`.
+async fn transfer_nft(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(id): Path<String>,
+    Json(payload): Json<NftTransferRequest>,
+) -> ApiResult<ApiResponse> {
+    if !session.matches(&payload.from) {
+        return Err(ApiError::session_mismatch("from"));
+    }
+
+    let mut nfts = state.nfts.write().await;
+    let transferred = nfts.transfer(&id, &payload.from, &payload.to);
+    drop(nfts);
+    transferred?;
+
+    let transaction = Transaction::new(
+        Some(payload.from.clone()),
+        payload.to.clone(),
+        0,
+        TransactionType::NftTransfer,
+    )
+    .with_reference(&id);
+    let logs = vec![EventLog::new(NFT_CONTRACT, "NftTransferred")
+        .with("id", &id)
+        .with("from", &payload.from)
+        .with("to", &payload.to)];
+    let message = format!(
+        "Transferred NFT {} from {} to {}",
+        id, payload.from, payload.to
+    );
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+/// Requires an authenticated session that owns the NFT.
+async fn burn_nft(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Path(id): Path<String>,
+) -> ApiResult<ApiResponse> {
+    let mut nfts = state.nfts.write().await;
+    let burned = nfts.burn(&id, &session.0);
+    drop(nfts);
+    let nft = burned?;
+
+    let transaction = Transaction::new(
+        Some(nft.owner.clone()),
+        String::new(),
+        0,
+        TransactionType::NftBurn,
+    )
+    .with_reference(&id);
+    let logs = vec![EventLog::new(NFT_CONTRACT, "NftBurned")
+        .with("id", &id)
+        .with("owner", &nft.owner)];
+    let message = format!("Burned NFT {}", id);
+    record_token_transaction(&state, transaction, logs, message).await
+}
+
+async fn get_nft(State(state): State<AppState>, Path(id): Path<String>) -> Json<Option<Nft>> {
+    let nfts = state.nfts.read().await;
+    Json(nfts.get(&id).cloned())
+}
+
+async fn get_account_nfts(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> Json<Vec<Nft>> {
+    let nfts = state.nfts.read().await;
+    Json(nfts.owned_by(&address).into_iter().cloned().collect())
+}
+
+// ===== SMART CONTRACT ENDPOINTS =====
+
+/// Requires an authenticated session (see `api_routes`). The session address
+/// deploys the contract and pays for gas.
+async fn deploy_contract(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<ContractDeployRequest>,
+) -> ApiResult<ContractExecutionResponse> {
+    let code = hex::decode(payload.code.trim_start_matches("0x"));
+    let input = hex::decode(payload.input.trim_start_matches("0x"));
+    let (Ok(code), Ok(input)) = (code, input) else {
+        return Err(ApiError::bad_request(
+            "invalid_hex",
+            "code and input must be hex-encoded",
+        ));
+    };
+    let invocation = Invocation {
+        caller: session.0.clone(),
+        input,
+        value: payload.value,
+        gas_limit: payload.gas_limit,
+    };
+    // The contract address is derived from the transaction id, so `to` is filled in after
+    let mut transaction = Transaction::new(
+        Some(session.0.clone()),
+        String::new(),
+        payload.value,
+        TransactionType::ContractDeploy,
+    );
+
+    // Lock order: see `AppState`
+    let mut tokens = state.tokens.write().await;
+    let mut contracts = state.contracts.write().await;
+    let executed =
+        contracts.deploy_contract(tokens.native_mut(), code, &invocation, &transaction.id);
+    let logs = contracts.take_logs();
+    drop(contracts);
+    drop(tokens);
+    let outcome = executed?;
+
+    transaction.to = outcome.contract.clone();
+    let transaction = transaction
+        .with_reference(&outcome.contract)
+        .with_fee(outcome.fee);
+    record_contract_transaction(&state, transaction, outcome, logs, "deployed").await
+}
+
+/// Requires an authenticated session (see `api_routes`). The session address
+/// is the caller and pays for gas.
+async fn call_contract(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
+    Json(payload): Json<ContractCallRequest>,
+) -> ApiResult<ContractExecutionResponse> {
+    let Ok(input) = hex::decode(payload.input.trim_start_matches("0x")) else {
+        return Err(ApiError::bad_request(
+            "invalid_hex",
+            "input must be hex-encoded",
+        ));
+    };
+    let invocation = Invocation {
+        caller: session.0.clone(),
+        input,
+        value: payload.value,
+        gas_limit: payload.gas_limit,
+    };
+
+    // Lock order: see `AppState`
+    let mut tokens = state.tokens.write().await;
+    let mut contracts = state.contracts.write().await;
+    let executed = contracts.call_contract(
+        tokens.native_mut(),
+        &payload.contract,
+        &payload.function,
+        &invocation,
+    );
+    let logs = contracts.take_logs();
+    drop(contracts);
+    drop(tokens);
+    let outcome = executed?;
+
+    let transaction = Transaction::new(
+        Some(session.0.clone()),
+        payload.contract.clone(),
+        payload.value,
+        TransactionType::ContractCall,
+    )
+    .with_reference(&payload.contract)
+    .with_fee(outcome.fee);
+    record_contract_transaction(&state, transaction, outcome, logs, "called").await
+}
+
+/// Adds a deploy or call to pending transactions. Failed runs are recorded
+/// too, since their gas fee was still charged, and answered with 200 and the
+/// failed outcome.
+async fn record_contract_transaction(
+    state: &AppState,
+    transaction: Transaction,
+    outcome: ExecutionOutcome,
+    logs: Vec<EventLog>,
+    action: &str,
+) -> ApiResult<ContractExecutionResponse> {
+    let mut pending = state.pending_transactions.write().await;
+    pending.push(transaction.clone());
+    drop(pending);
+    let receipt =
+        Receipt::new(&transaction, logs).with_execution(outcome.success, outcome.gas_used);
+    record_receipt(state, receipt).await;
+
+    persist_state(state);
+    state
+        .events
+        .publish(ChainEvent::PendingTransaction(transaction));
+
+    Ok(Json(ContractExecutionResponse {
+        status: if outcome.success { "success" } else { "error" }.to_string(),
+        message: match &outcome.error {
+            None => format!(
+                "Contract {} {} using {} gas",
+                outcome.contract, action, outcome.gas_used
+            ),
+            Some(error) => format!("Contract {} failed: {}", outcome.contract, error),
+        },
+        outcome: Some(outcome),
+    }))
+}
+
+async fn get_contract(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> Json<Option<WasmContract>> {
+    let contracts = state.contracts.read().await;
+    Json(contracts.wasm_contracts.get(&address).cloned())
+}
+
+// ===== EVENT ENDPOINTS =====
+
+/// Mined contract logs. `type`, `contract`, `from_block`, `to_block` and
+/// `limit` are reserved; any other key filters on a log attribute, e.g.
+/// `?type=CertificateIssued&holder=0xabc`.
+async fn query_events(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> ApiResult<EventQueryResponse> {
+    let from_block = parse_param::<u64>(&params, "from_block")?;
+    let to_block = parse_param::<u64>(&params, "to_block")?;
+    let limit = parse_param::<usize>(&params, "limit")?;
+
+    let query = LogQuery {
+        topic: params.get("type").cloned(),
+        contract: params.get("contract").cloned(),
+        from_block,
+        to_block,
+        attributes: params
+            .iter()
+            .filter(|(key, _)| !EVENT_QUERY_KEYS.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<BTreeMap<_, _>>(),
+    };
+    let receipts = state.receipts.read().await;
+    let events = receipts.query_logs(&query, page_bounds(None, limit).1);
+    Ok(Json(EventQueryResponse {
+        status: "success".to_string(),
+        message: format!("Found {} events", events.len()),
+        events,
+    }))
+}
+
+fn parse_param<T: std::str::FromStr>(
+    params: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<T>, ApiError> {
+    params
+        .get(key)
+        .map(|value| {
+            value.parse().map_err(|_| {
+                ApiError::bad_request("invalid_parameter", format!("Invalid {}: {}", key, value))
+            })
+        })
+        .transpose()
+}
+
+async fn get_receipt(
+    State(state): State<AppState>,
+    Path(transaction_id): Path<String>,
+) -> Json<Option<Receipt>> {
+    let receipts = state.receipts.read().await;
+    Json(receipts.get(&transaction_id).cloned())
+}
+
+async fn get_block_receipts(
+    State(state): State<AppState>,
+    Path(index): Path<u64>,
+) -> Json<Vec<Receipt>> {
+    let receipts = state.receipts.read().await;
+    Json(receipts.block(index).to_vec())
 }
 
 // ===== BLOCKCHAIN ENDPOINTS =====
 
-async fn get_blocks(State(state): State<AppState>) -> Json<Vec<String>> {
-    let chain = state.chain.lock().await;
-    let block_data: Vec<String> = chain.blocks.iter().map(|b| format!("{:?}", b)).collect();
-    Json(block_data)
+async fn get_blocks(
+    State(state): State<AppState>,
+    Query(query): Query<BlockPageQuery>,
+) -> Json<Vec<Block>> {
+    let (from, limit) = query.bounds();
+    let chain = state.chain.read().await;
+    Json(chain.blocks_page(from, limit).to_vec())
+}
+
+async fn get_headers(
+    State(state): State<AppState>,
+    Query(query): Query<BlockPageQuery>,
+) -> Json<Vec<BlockHeader>> {
+    let (from, limit) = query.bounds();
+    let chain = state.chain.read().await;
+    Json(
+        chain
+            .blocks_page(from, limit)
+            .iter()
+            .map(Block::header)
+            .collect(),
+    )
 }
 
 async fn get_block_by_index(
     State(state): State<AppState>,
     Path(index): Path<usize>,
-) -> Json<Option<String>> {
-    let chain = state.chain.lock().await;
+) -> Json<Option<Block>> {
+    let chain = state.chain.read().await;
+    Json(chain.blocks.get(index).cloned())
+}
 
-    if index < chain.blocks.len() {
-        Json(Some(format!("{:?}", chain.blocks[index])))
-    } else {
-        Json(None)
-    }
+async fn get_block_by_hash(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+) -> Json<Option<Block>> {
+    let chain = state.chain.read().await;
+    Json(chain.block_by_hash(&hash).cloned())
 }
 
 async fn get_blockchain_stats(State(state): State<AppState>) -> Json<BlockchainStats> {
-    let chain = state.chain.lock().await;
-    let pending = state.pending_transactions.lock().await;
-    let governance = state.governance.lock().await;
+    let chain = state.chain.read().await;
+    let pending = state.pending_transactions.read().await;
+    let governance = state.governance.read().await;
 
     Json(BlockchainStats {
         total_blocks: chain.blocks.len(),
         pending_transactions: pending.len(),
-        difficulty: chain.difficulty,
+        difficulty: chain.next_difficulty(),
         is_valid: chain.is_valid(),
         latest_hash: chain.latest_hash(),
         finalized_height: chain.finalized_height,
@@ -452,88 +2459,48 @@ async fn get_blockchain_stats(State(state): State<AppState>) -> Json<BlockchainS
 
 // ===== MINING ENDPOINTS =====
 
-async fn mine_block(State(state): State<AppState>) -> Json<MiningResponse> {
+async fn mine_block(State(state): State<AppState>) -> ApiResult<MiningResponse> {
     let finality_depth = {
-        let governance = state.governance.lock().await;
+        let governance = state.governance.read().await;
         governance.finality_depth
     };
-    let mut pending = state.pending_transactions.lock().await;
-    let mut chain = state.chain.lock().await;
-    let stakers = state.stakers.lock().await;
+    let chain = state.chain.read().await;
+    let pending = state.pending_transactions.read().await;
+    let stakers = state.stakers.read().await;
 
     // Since genesis block is auto-created, we only need to check for pending transactions
     // Allow mining if there are pending transactions OR if there's only the genesis block
     let has_only_genesis = chain.blocks.len() == 1;
-
-    if pending.is_empty() && !has_only_genesis {
-        drop(chain);
-        drop(pending);
-        drop(stakers);
-        return Json(MiningResponse {
-            status: "info".to_string(),
-            message: "No pending transactions to mine".to_string(),
-            block_index: 0,
-            transactions_count: 0,
-        });
-    }
-
-    let seed = chain.latest_hash();
-    let validator = pos::select_staker_with_seed(&seed, &stakers);
-    if validator.is_none() {
-        drop(chain);
-        drop(pending);
-        drop(stakers);
-        return Json(MiningResponse {
-            status: "error".to_string(),
-            message: "No validators available. Stake tokens to become a validator.".to_string(),
+
+    if pending.is_empty() && !has_only_genesis {
+        return Ok(Json(MiningResponse {
+            status: "info".to_string(),
+            message: "No pending transactions to mine".to_string(),
             block_index: 0,
             transactions_count: 0,
-        });
+            job_id: None,
+        }));
     }
 
-    let validator = validator.unwrap();
-    let staker_entry = match stakers.iter().find(|staker| staker.address == validator) {
-        Some(value) => value,
-        None => {
-            drop(chain);
-            drop(pending);
-            drop(stakers);
-            return Json(MiningResponse {
-                status: "error".to_string(),
-                message: "Selected validator not registered".to_string(),
-                block_index: 0,
-                transactions_count: 0,
-            });
-        }
-    };
-    let public_key = match &staker_entry.public_key {
-        Some(value) => value.clone(),
-        None => {
-            drop(chain);
-            drop(pending);
-            drop(stakers);
-            return Json(MiningResponse {
-                status: "error".to_string(),
-                message: "Validator missing public key".to_string(),
-                block_index: 0,
-                transactions_count: 0,
-            });
-        }
-    };
-    let private_key = match &staker_entry.private_key {
-        Some(value) => value.clone(),
-        None => {
-            drop(chain);
-            drop(pending);
-            drop(stakers);
-            return Json(MiningResponse {
-                status: "error".to_string(),
-                message: "Validator missing private key".to_string(),
-                block_index: 0,
-                transactions_count: 0,
-            });
-        }
-    };
+    let seed = chain.latest_hash();
+    let validator = pos::select_staker_with_seed(&seed, &stakers).ok_or_else(|| {
+        ApiError::conflict(
+            "no_validators",
+            "No validators available. Stake tokens to become a validator.",
+        )
+    })?;
+    let staker_entry = stakers
+        .iter()
+        .find(|staker| staker.address == validator)
+        .ok_or_else(|| ApiError::internal("Selected validator not registered"))?;
+    let public_key = staker_entry
+        .public_key
+        .clone()
+        .ok_or_else(|| ApiError::internal("Validator missing public key"))?;
+    // Validators staked through another node cannot sign here
+    let private_key = staker_entry.private_key.clone().ok_or_else(|| {
+        ApiError::conflict("validator_not_local", "Validator missing private key")
+    })?;
     let staker_snapshot: Vec<Staker> = stakers
         .iter()
         .map(|staker| Staker {
@@ -545,62 +2512,203 @@ async fn mine_block(State(state): State<AppState>) -> Json<MiningResponse> {
         .collect();
     let staker_set_hash = pos::staker_set_hash(&staker_snapshot);
 
-    let transactions_count: usize;
-    let transaction_strings: Vec<String>;
-
-    if has_only_genesis && pending.is_empty() {
+    let transactions: Vec<BlockTransaction> = if has_only_genesis && pending.is_empty() {
         // For the first user-initiated mining after genesis, create a welcome transaction
-        transaction_strings = vec![
-            "First mined block - Blockchain is now active!".to_string(),
-            format!("Validator: {}", validator),
-        ];
-        transactions_count = transaction_strings.len();
+        vec![
+            "First mined block - Blockchain is now active!".into(),
+            BlockTransaction::Note(format!("Validator: {}", validator)),
+        ]
     } else {
-        // Convert pending transactions to strings for the block
-        transaction_strings = pending.iter().map(|tx| format!("{:?}", tx)).collect();
-        transactions_count = transaction_strings.len();
-
-        // Clear pending transactions after copying them
-        pending.clear();
-    }
+        pending
+            .iter()
+            .cloned()
+            .map(BlockTransaction::from)
+            .collect()
+    };
+    let transactions_count = transactions.len();
+    // (certificate id or batch root, transaction id, is batch)
+    let certificate_refs: Vec<(String, String, bool)> = pending
+        .iter()
+        .filter(|tx| {
+            matches!(
+                tx.transaction_type,
+                TransactionType::Certificate
+                    | TransactionType::CertificateStatus
+                    | TransactionType::CertificateBatch
+                    | TransactionType::Reward
+            )
+        })
+        .filter_map(|tx| {
+            let is_batch = matches!(tx.transaction_type, TransactionType::CertificateBatch);
+            Some((tx.reference.clone()?, tx.id.clone(), is_batch))
+        })
+        .collect();
+    let mined_ids: HashSet<String> = pending.iter().map(|tx| tx.id.clone()).collect();
 
-    let mut block = chain.create_block(
-        transaction_strings,
+    let template = chain.block_template(
+        transactions,
         Some(validator.clone()),
         Some(public_key),
         Some(staker_set_hash),
         Some(staker_snapshot),
     );
-    let signature = match pos::sign_block_hash(&block.hash, &private_key) {
-        Ok(value) => value,
+    drop(stakers);
+    drop(pending);
+    drop(chain);
+
+    let mut mining = state.mining.write().await;
+    let (job_id, cancel) = mining.start(&template).map_err(|active| {
+        ApiError::conflict(
+            "mining_in_progress",
+            format!("Mining job {} is still running", active.id),
+        )
+    })?;
+    drop(mining);
+    let work = MiningWork {
+        job_id: job_id.clone(),
+        template,
+        private_key,
+        certificate_refs,
+        mined_ids,
+        finality_depth,
+    };
+    // Spawned so the job still lands if the client disconnects
+    let block_index = tokio::spawn(run_mining_job(state.clone(), work, cancel))
+        .await
+        .map_err(|err| ApiError::internal(format!("Mining task failed: {}", err)))??;
+
+    Ok(Json(MiningResponse {
+        status: "success".to_string(),
+        message: if has_only_genesis {
+            format!(
+                "Successfully mined the first block! 🎉 Validator {} secured the block.",
+                validator
+            )
+        } else {
+            format!(
+                "Successfully mined block with {} transactions. Validator: {}",
+                transactions_count, validator
+            )
+        },
+        block_index,
+        transactions_count,
+        job_id: Some(job_id),
+    }))
+}
+
+/// A block template and what to do with it once mined.
+struct MiningWork {
+    job_id: String,
+    template: Block,
+    private_key: String,
+    // (certificate id or batch root, transaction id, is batch)
+    certificate_refs: Vec<(String, String, bool)>,
+    mined_ids: HashSet<String>,
+    finality_depth: u64,
+}
+
+/// Searches for the block's proof of work on the blocking pool, then appends
+/// it if the chain tip has not moved. Returns the new block's index.
+async fn run_mining_job(
+    state: AppState,
+    work: MiningWork,
+    cancel: Arc<AtomicBool>,
+) -> Result<u64, ApiError> {
+    let MiningWork {
+        job_id,
+        template,
+        private_key,
+        certificate_refs,
+        mined_ids,
+        finality_depth,
+    } = work;
+
+    let mined = tokio::task::spawn_blocking(move || {
+        let mut block = template;
+        block.mine(&cancel).map(|found| found.then_some(block))
+    })
+    .await;
+    let outcome = match mined {
+        Ok(Ok(Some(block))) => Ok(block),
+        Ok(Ok(None)) => return Err(cancel_mining_job(&state, &job_id).await),
+        Ok(Err(err)) => Err(format!("Cannot mine block: {}", err)),
+        Err(err) => Err(format!("Mining task failed: {}", err)),
+    };
+    let mut block = match outcome {
+        Ok(block) => block,
+        Err(reason) => {
+            let status = MiningStatus::Failed {
+                reason: reason.clone(),
+            };
+            state.mining.write().await.finish(&job_id, status);
+            return Err(ApiError::internal(reason));
+        }
+    };
+    match pos::sign_block_hash(&block.hash, &private_key) {
+        Ok(signature) => block.validator_signature = Some(signature),
         Err(message) => {
-            drop(chain);
-            drop(pending);
-            drop(stakers);
-            return Json(MiningResponse {
-                status: "error".to_string(),
-                message: format!("Failed to sign block: {}", message),
-                block_index: 0,
-                transactions_count: 0,
-            });
+            let reason = format!("Failed to sign block: {}", message);
+            let status = MiningStatus::Failed {
+                reason: reason.clone(),
+            };
+            state.mining.write().await.finish(&job_id, status);
+            return Err(ApiError::internal(reason));
         }
+    }
+
+    let mut chain = state.chain.write().await;
+    let mut pending = state.pending_transactions.write().await;
+    if chain.latest_hash() != block.previous_hash {
+        drop(pending);
+        drop(chain);
+        return Err(cancel_mining_job(&state, &job_id).await);
+    }
+    let mined_status = MiningStatus::Mined {
+        hash: block.hash.clone(),
+        nonce: block.nonce,
     };
-    block.validator_signature = Some(signature);
+    let previous_finalized = chain.finalized_height;
     chain.add_mined_block(block);
     chain.apply_finality(finality_depth);
+    let finalized_height = chain.finalized_height;
     let block_index = chain.blocks.len() as u64 - 1;
     let block_to_gossip = chain.blocks.last().cloned();
+    // Transactions submitted while mining wait for the next block
+    pending.retain(|tx| !mined_ids.contains(&tx.id));
 
     // Release locks
-    drop(chain);
     drop(pending);
-    drop(stakers);
+    drop(chain);
 
-    let mut metrics = state.metrics.lock().await;
+    if let Some(block) = &block_to_gossip {
+        let mut contracts = state.contracts.write().await;
+        for (reference, transaction_id, is_batch) in certificate_refs {
+            let anchor = BlockAnchor {
+                block_index: block.index,
+                block_hash: block.hash.clone(),
+                transaction_id,
+            };
+            if is_batch {
+                contracts.anchor_batch(&reference, anchor);
+            } else {
+                contracts.anchor_certificate(&reference, anchor);
+            }
+        }
+        drop(contracts);
+        let mut receipts = state.receipts.write().await;
+        receipts.seal(block.index, &block.hash, &mined_ids);
+    }
+
+    state.mining.write().await.finish(&job_id, mined_status);
+    let mut metrics = state.metrics.write().await;
     metrics.blocks_mined += 1;
     drop(metrics);
 
-    let _ = persist_state(&state).await;
+    persist_state(&state);
+    if let Some(block) = &block_to_gossip {
+        publish_block(&state, block);
+    }
+    publish_finality(&state, previous_finalized, finalized_height);
     if let Some(block) = block_to_gossip {
         let state_clone = state.clone();
         tokio::spawn(async move {
@@ -608,80 +2716,132 @@ async fn mine_block(State(state): State<AppState>) -> Json<MiningResponse> {
         });
     }
 
-    Json(MiningResponse {
-        status: "success".to_string(),
-        message: if has_only_genesis {
-            format!(
-                "Successfully mined the first block! 🎉 Validator {} secured the block.",
-                validator
-            )
-        } else {
-            format!(
-                "Successfully mined block with {} transactions. Validator: {}",
-                transactions_count, validator
-            )
-        },
-        block_index,
-        transactions_count,
-    })
+    Ok(block_index)
 }
 
-async fn get_mining_difficulty(State(state): State<AppState>) -> Json<DifficultyResponse> {
-    let chain = state.chain.lock().await;
-    Json(DifficultyResponse {
-        current_difficulty: chain.difficulty,
-    })
+/// Marks a job whose parent is no longer the chain tip as cancelled.
+async fn cancel_mining_job(state: &AppState, job_id: &str) -> ApiError {
+    let reason = state
+        .mining
+        .write()
+        .await
+        .finish_cancelled(job_id, "A competing block extended the chain first");
+    ApiError::conflict("mining_cancelled", reason)
 }
 
-async fn set_mining_difficulty(
-    State(state): State<AppState>,
-    Json(payload): Json<DifficultyRequest>,
-) -> Json<ApiResponse> {
-    let mut chain = state.chain.lock().await;
-    let old_difficulty = chain.difficulty;
-    chain.difficulty = payload.difficulty;
+/// The transactions a block accepted from a peer mined.
+struct SettledBlock {
+    index: u64,
+    hash: String,
+    transaction_ids: HashSet<String>,
+}
 
-    let _ = persist_state(&state).await;
+impl SettledBlock {
+    fn new(block: &Block) -> Self {
+        SettledBlock {
+            index: block.index,
+            hash: block.hash.clone(),
+            transaction_ids: block
+                .transactions
+                .iter()
+                .filter_map(BlockTransaction::as_transaction)
+                .map(|tx| tx.id.clone())
+                .collect(),
+        }
+    }
+}
 
-    Json(ApiResponse {
-        status: "success".to_string(),
-        message: format!(
-            "Mining difficulty changed from {} to {}",
-            old_difficulty, payload.difficulty
-        ),
+/// Treats the transactions of blocks accepted from peers as mined, the way
+/// `run_mining_job` does for local blocks: they leave the pending pool and
+/// their receipts move under the block.
+async fn settle_peer_blocks(state: &AppState, blocks: &[SettledBlock]) {
+    if blocks.is_empty() {
+        return;
+    }
+    let mut pending = state.pending_transactions.write().await;
+    pending.retain(|tx| {
+        !blocks
+            .iter()
+            .any(|block| block.transaction_ids.contains(&tx.id))
+    });
+    drop(pending);
+    let mut receipts = state.receipts.write().await;
+    for block in blocks {
+        receipts.seal(block.index, &block.hash, &block.transaction_ids);
+    }
+}
+
+/// Stops the local mining job once a peer's block reaches `height`.
+/// The job records the block as its cancel reason; see `/mining/jobs`.
+async fn cancel_stale_mining(state: &AppState, height: u64) {
+    state.mining.write().await.cancel_stale(height);
+}
+
+async fn list_mining_jobs(State(state): State<AppState>) -> Json<Vec<MiningJob>> {
+    let mining = state.mining.read().await;
+    Json(mining.list().cloned().collect())
+}
+
+async fn get_mining_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<MiningJob> {
+    let mining = state.mining.read().await;
+    mining
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("mining_job_not_found", format!("No mining job {}", id)))
+}
+
+async fn get_mining_difficulty(State(state): State<AppState>) -> Json<DifficultyResponse> {
+    let bits = state.chain.read().await.next_difficulty();
+    Json(DifficultyResponse {
+        current_difficulty: bits,
+        target: pow::target_from_bits(bits)
+            .map(hex::encode)
+            .unwrap_or_default(),
+        expected_hashes: difficulty::expected_hashes(bits).unwrap_or_default(),
+        target_block_seconds: difficulty::TARGET_BLOCK_SECONDS,
+        retarget_interval: difficulty::RETARGET_INTERVAL,
     })
 }
 
 // ===== STAKING ENDPOINTS =====
 
+/// Requires [`Role::ValidatorOperator`] (see `api_routes`).
 async fn stake_tokens(
     State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
     Json(payload): Json<StakeRequest>,
-) -> Json<StakeResponse> {
+) -> ApiResult<StakeResponse> {
+    if !session.matches(&payload.address) {
+        return Err(ApiError::session_mismatch("address"));
+    }
     if payload.amount == 0 {
-        return Json(StakeResponse {
-            status: "error".to_string(),
-            message: "Stake amount must be greater than zero".to_string(),
-            total_stake: 0,
-        });
+        return Err(ApiError::bad_request(
+            "invalid_amount",
+            "Stake amount must be greater than zero",
+        ));
     }
 
-    let mut token = state.token.lock().await;
-    let transfer_success = token.transfer(&payload.address, STAKING_POOL_ACCOUNT, payload.amount);
-    drop(token);
-
-    if !transfer_success {
-        return Json(StakeResponse {
-            status: "error".to_string(),
-            message: format!("Insufficient balance to stake for {}", payload.address),
-            total_stake: 0,
-        });
+    let mut tokens = state.tokens.write().await;
+    let mut stakers = state.stakers.write().await;
+    let registered = stakers
+        .iter()
+        .any(|staker| staker.address == payload.address);
+    if !registered && (payload.public_key.is_none() || payload.private_key.is_none()) {
+        return Err(ApiError::bad_request(
+            "validator_keys_required",
+            "Validator registration requires public_key and private_key",
+        ));
     }
+    tokens
+        .native_mut()
+        .transfer(&payload.address, STAKING_POOL_ACCOUNT, payload.amount)?;
+    drop(tokens);
 
-    let mut stakers = state.stakers.lock().await;
     let mut total_stake = 0;
-    let mut found = false;
-
     for staker in stakers.iter_mut() {
         if staker.address == payload.address {
             staker.stake += payload.amount;
@@ -691,19 +2851,11 @@ async fn stake_tokens(
             if payload.private_key.is_some() {
                 staker.private_key = payload.private_key.clone();
             }
-            found = true;
         }
         total_stake += staker.stake;
     }
 
-    if !found {
-        if payload.public_key.is_none() || payload.private_key.is_none() {
-            return Json(StakeResponse {
-                status: "error".to_string(),
-                message: "Validator registration requires public_key and private_key".to_string(),
-                total_stake,
-            });
-        }
+    if !registered {
         stakers.push(Staker {
             address: payload.address.clone(),
             stake: payload.amount,
@@ -712,69 +2864,59 @@ async fn stake_tokens(
         });
         total_stake += payload.amount;
     }
+    drop(stakers);
 
-    let _ = persist_state(&state).await;
+    persist_state(&state);
 
-    Json(StakeResponse {
+    Ok(Json(StakeResponse {
         status: "success".to_string(),
         message: format!("Staked {} tokens for {}", payload.amount, payload.address),
         total_stake,
-    })
+    }))
 }
 
+/// Requires an authenticated session (see `api_routes`).
 async fn withdraw_stake(
     State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedAddress>,
     Json(payload): Json<StakeRequest>,
-) -> Json<StakeResponse> {
+) -> ApiResult<StakeResponse> {
+    if !session.matches(&payload.address) {
+        return Err(ApiError::session_mismatch("address"));
+    }
     if payload.amount == 0 {
-        return Json(StakeResponse {
-            status: "error".to_string(),
-            message: "Withdraw amount must be greater than zero".to_string(),
-            total_stake: 0,
-        });
+        return Err(ApiError::bad_request(
+            "invalid_amount",
+            "Withdraw amount must be greater than zero",
+        ));
     }
 
-    let mut stakers = state.stakers.lock().await;
-    let mut total_stake = 0;
-    let mut available_stake = None;
-
-    for staker in stakers.iter() {
-        total_stake += staker.stake;
-        if staker.address == payload.address {
-            available_stake = Some(staker.stake);
-        }
-    }
+    let mut tokens = state.tokens.write().await;
+    let mut stakers = state.stakers.write().await;
+    let available_stake = stakers
+        .iter()
+        .find(|staker| staker.address == payload.address)
+        .map(|staker| staker.stake);
 
-    let available_stake = match available_stake {
-        Some(stake) => stake,
-        None => {
-            return Json(StakeResponse {
-                status: "error".to_string(),
-                message: format!("No stake found for {}", payload.address),
-                total_stake,
-            });
-        }
-    };
+    let available_stake = available_stake.ok_or_else(|| {
+        ApiError::not_found(
+            "stake_not_found",
+            format!("No stake found for {}", payload.address),
+        )
+    })?;
 
     if available_stake < payload.amount {
-        return Json(StakeResponse {
-            status: "error".to_string(),
-            message: format!("Insufficient staked balance for {}", payload.address),
-            total_stake,
-        });
+        return Err(ApiError::unprocessable(
+            "insufficient_stake",
+            format!("Insufficient staked balance for {}", payload.address),
+        ));
     }
 
-    let mut token = state.token.lock().await;
-    let transfer_success = token.transfer(STAKING_POOL_ACCOUNT, &payload.address, payload.amount);
-    drop(token);
-
-    if !transfer_success {
-        return Json(StakeResponse {
-            status: "error".to_string(),
-            message: "Staking pool has insufficient balance".to_string(),
-            total_stake,
-        });
-    }
+    tokens
+        .native_mut()
+        .transfer(STAKING_POOL_ACCOUNT, &payload.address, payload.amount)
+        .map_err(|_| ApiError::internal("Staking pool has insufficient balance"))?;
+    drop(tokens);
 
     let mut updated_total = 0;
     stakers.retain(|staker| {
@@ -789,21 +2931,22 @@ async fn withdraw_stake(
         updated_total += staker.stake;
         true
     });
+    drop(stakers);
 
-    let _ = persist_state(&state).await;
+    persist_state(&state);
 
-    Json(StakeResponse {
+    Ok(Json(StakeResponse {
         status: "success".to_string(),
         message: format!(
             "Withdrew {} staked tokens for {}",
             payload.amount, payload.address
         ),
         total_stake: updated_total,
-    })
+    }))
 }
 
 async fn list_validators(State(state): State<AppState>) -> Json<Vec<ValidatorInfo>> {
-    let stakers = state.stakers.lock().await;
+    let stakers = state.stakers.read().await;
     let validators = stakers
         .iter()
         .map(|staker| ValidatorInfo {
@@ -817,239 +2960,245 @@ async fn list_validators(State(state): State<AppState>) -> Json<Vec<ValidatorInf
 
 // ===== P2P ENDPOINTS =====
 
-async fn list_peers(State(state): State<AppState>, headers: HeaderMap) -> Json<Vec<String>> {
+async fn list_peers(State(state): State<AppState>, headers: HeaderMap) -> ApiResult<Vec<String>> {
     if !authorize_p2p(&headers, &state) {
-        return Json(Vec::new());
+        return Err(ApiError::unauthorized("Unauthorized peer request"));
     }
-    let peers = state.peers.lock().await;
-    Json(peers.clone())
+    let peers = state.peers.read().await;
+    Ok(Json(peers.clone()))
 }
 
 async fn register_peer(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(payload): Json<PeerRequest>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
     if !authorize_p2p(&headers, &state) {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Unauthorized peer request".to_string(),
-        });
+        return Err(ApiError::unauthorized("Unauthorized peer request"));
     }
     if payload.address.trim().is_empty() {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Peer address cannot be empty".to_string(),
-        });
+        return Err(ApiError::bad_request(
+            "invalid_peer_address",
+            "Peer address cannot be empty",
+        ));
     }
 
-    let mut peers = state.peers.lock().await;
+    let mut peers = state.peers.write().await;
     if !peers.contains(&payload.address) {
         peers.push(payload.address.clone());
-        let mut metrics = state.metrics.lock().await;
+        let mut metrics = state.metrics.write().await;
         metrics.peers_registered += 1;
     }
     drop(peers);
-    let _ = persist_state(&state).await;
+    persist_state(&state);
 
-    Json(ApiResponse {
+    Ok(Json(ApiResponse {
         status: "success".to_string(),
         message: format!("Registered peer {}", payload.address),
-    })
+    }))
 }
 
 async fn receive_block(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(block): Json<Block>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
     if !authorize_p2p(&headers, &state) {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Unauthorized peer request".to_string(),
-        });
+        return Err(ApiError::unauthorized("Unauthorized peer request"));
     }
     let finality_depth = {
-        let governance = state.governance.lock().await;
+        let governance = state.governance.read().await;
         governance.finality_depth
     };
-    let mut chain = state.chain.lock().await;
+    let mut chain = state.chain.write().await;
 
-    if let Err(message) = chain.validate_block_candidate(&block) {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message,
-        });
-    }
+    chain.validate_block_candidate(&block)?;
 
+    let previous_finalized = chain.finalized_height;
+    publish_block(&state, &block);
+    let settled = SettledBlock::new(&block);
     chain.add_mined_block(block);
     chain.apply_finality(finality_depth);
+    publish_finality(&state, previous_finalized, chain.finalized_height);
+    let height = chain.blocks.len() as u64 - 1;
     drop(chain);
-    let mut metrics = state.metrics.lock().await;
+    settle_peer_blocks(&state, &[settled]).await;
+    cancel_stale_mining(&state, height).await;
+    let mut metrics = state.metrics.write().await;
     metrics.blocks_received += 1;
-    let _ = persist_state(&state).await;
+    drop(metrics);
+    persist_state(&state);
 
-    Json(ApiResponse {
+    Ok(Json(ApiResponse {
         status: "success".to_string(),
         message: "Block accepted".to_string(),
-    })
+    }))
 }
 
 async fn receive_blocks(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(blocks): Json<Vec<Block>>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
     if !authorize_p2p(&headers, &state) {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Unauthorized peer request".to_string(),
-        });
+        return Err(ApiError::unauthorized("Unauthorized peer request"));
     }
     let finality_depth = {
-        let governance = state.governance.lock().await;
+        let governance = state.governance.read().await;
         governance.finality_depth
     };
-    let mut chain = state.chain.lock().await;
-    let mut accepted = 0u64;
+    let mut chain = state.chain.write().await;
+    let previous_finalized = chain.finalized_height;
+    let mut settled = Vec::new();
 
     for block in blocks {
         if chain.validate_block_candidate(&block).is_ok() {
+            publish_block(&state, &block);
+            settled.push(SettledBlock::new(&block));
             chain.add_mined_block(block);
-            accepted += 1;
         } else {
             break;
         }
     }
+    let accepted = settled.len() as u64;
     if accepted > 0 {
         chain.apply_finality(finality_depth);
+        publish_finality(&state, previous_finalized, chain.finalized_height);
     }
+    let height = chain.blocks.len() as u64 - 1;
     drop(chain);
+    settle_peer_blocks(&state, &settled).await;
+    cancel_stale_mining(&state, height).await;
 
     if accepted > 0 {
-        let mut metrics = state.metrics.lock().await;
+        let mut metrics = state.metrics.write().await;
         metrics.blocks_received += accepted;
-        let _ = persist_state(&state).await;
+        persist_state(&state);
     }
 
-    Json(ApiResponse {
+    Ok(Json(ApiResponse {
         status: "success".to_string(),
         message: format!("Accepted {} blocks", accepted),
-    })
+    }))
 }
 
 async fn receive_protocol_message(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(envelope): Json<P2PEnvelope>,
-) -> Json<ApiResponse> {
+) -> ApiResult<ApiResponse> {
     if !authorize_p2p(&headers, &state) {
-        let mut metrics = state.metrics.lock().await;
+        let mut metrics = state.metrics.write().await;
         metrics.protocol_messages_rejected += 1;
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Unauthorized peer request".to_string(),
-        });
+        return Err(ApiError::unauthorized("Unauthorized peer request"));
     }
 
     if let Err(message) = envelope.validate(300) {
-        let mut metrics = state.metrics.lock().await;
+        let mut metrics = state.metrics.write().await;
         metrics.protocol_messages_rejected += 1;
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message,
-        });
+        return Err(ApiError::bad_request("invalid_envelope", message));
     }
 
     {
-        let mut metrics = state.metrics.lock().await;
+        let mut metrics = state.metrics.write().await;
         metrics.protocol_messages_received += 1;
     }
 
     match envelope.payload {
-        P2PPayload::Ping => Json(ApiResponse {
+        P2PPayload::Ping => Ok(Json(ApiResponse {
             status: "success".to_string(),
             message: "pong".to_string(),
-        }),
+        })),
         P2PPayload::PeerAnnounce { address } => {
             if address.trim().is_empty() {
-                let mut metrics = state.metrics.lock().await;
+                let mut metrics = state.metrics.write().await;
                 metrics.protocol_messages_rejected += 1;
-                return Json(ApiResponse {
-                    status: "error".to_string(),
-                    message: "Peer address cannot be empty".to_string(),
-                });
+                return Err(ApiError::bad_request(
+                    "invalid_peer_address",
+                    "Peer address cannot be empty",
+                ));
             }
 
-            let mut peers = state.peers.lock().await;
+            let mut peers = state.peers.write().await;
             if !peers.contains(&address) {
                 peers.push(address.clone());
-                let mut metrics = state.metrics.lock().await;
+                let mut metrics = state.metrics.write().await;
                 metrics.peers_registered += 1;
             }
             drop(peers);
-            let _ = persist_state(&state).await;
+            persist_state(&state);
 
-            Json(ApiResponse {
+            Ok(Json(ApiResponse {
                 status: "success".to_string(),
                 message: format!("Registered peer {}", address),
-            })
+            }))
         }
         P2PPayload::Block(block) => {
             let finality_depth = {
-                let governance = state.governance.lock().await;
+                let governance = state.governance.read().await;
                 governance.finality_depth
             };
-            let mut chain = state.chain.lock().await;
-            if let Err(message) = chain.validate_block_candidate(&block) {
-                let mut metrics = state.metrics.lock().await;
+            let mut chain = state.chain.write().await;
+            if let Err(err) = chain.validate_block_candidate(&block) {
+                let mut metrics = state.metrics.write().await;
                 metrics.protocol_messages_rejected += 1;
-                return Json(ApiResponse {
-                    status: "error".to_string(),
-                    message,
-                });
+                return Err(err.into());
             }
+            let previous_finalized = chain.finalized_height;
+            publish_block(&state, &block);
+            let settled = SettledBlock::new(&block);
             chain.add_mined_block(block);
             chain.apply_finality(finality_depth);
+            publish_finality(&state, previous_finalized, chain.finalized_height);
+            let height = chain.blocks.len() as u64 - 1;
             drop(chain);
-            let mut metrics = state.metrics.lock().await;
+            settle_peer_blocks(&state, &[settled]).await;
+            cancel_stale_mining(&state, height).await;
+            let mut metrics = state.metrics.write().await;
             metrics.blocks_received += 1;
             drop(metrics);
-            let _ = persist_state(&state).await;
-            Json(ApiResponse {
+            persist_state(&state);
+            Ok(Json(ApiResponse {
                 status: "success".to_string(),
                 message: "Block accepted".to_string(),
-            })
+            }))
         }
         P2PPayload::BlockBatch(blocks) => {
             let finality_depth = {
-                let governance = state.governance.lock().await;
+                let governance = state.governance.read().await;
                 governance.finality_depth
             };
-            let mut chain = state.chain.lock().await;
-            let mut accepted = 0u64;
+            let mut chain = state.chain.write().await;
+            let previous_finalized = chain.finalized_height;
+            let mut settled = Vec::new();
             for block in blocks {
                 if chain.validate_block_candidate(&block).is_ok() {
+                    publish_block(&state, &block);
+                    settled.push(SettledBlock::new(&block));
                     chain.add_mined_block(block);
-                    accepted += 1;
                 } else {
                     break;
                 }
             }
+            let accepted = settled.len() as u64;
             if accepted > 0 {
                 chain.apply_finality(finality_depth);
+                publish_finality(&state, previous_finalized, chain.finalized_height);
             }
+            let height = chain.blocks.len() as u64 - 1;
             drop(chain);
+            settle_peer_blocks(&state, &settled).await;
+            cancel_stale_mining(&state, height).await;
             if accepted > 0 {
-                let mut metrics = state.metrics.lock().await;
+                let mut metrics = state.metrics.write().await;
                 metrics.blocks_received += accepted;
                 drop(metrics);
-                let _ = persist_state(&state).await;
+                persist_state(&state);
             }
-            Json(ApiResponse {
+            Ok(Json(ApiResponse {
                 status: "success".to_string(),
                 message: format!("Accepted {} blocks", accepted),
-            })
+            }))
         }
     }
 }
@@ -1057,134 +3206,238 @@ async fn receive_protocol_message(
 // ===== GOVERNANCE & SLASHING ENDPOINTS =====
 
 async fn get_governance(State(state): State<AppState>) -> Json<GovernanceResponse> {
-    let governance = state.governance.lock().await;
+    let governance = state.governance.read().await;
     Json(GovernanceResponse {
         slash_percent: governance.slash_percent,
         finality_depth: governance.finality_depth,
     })
 }
 
+/// Requires [`Role::Admin`] (see `api_routes`).
+/// Grants or revokes the right to mint the native token.
+async fn set_mint_authority(
+    State(state): State<AppState>,
+    Json(payload): Json<TokenMinterRequest>,
+) -> Json<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let native = tokens.native_mut();
+    native.set_mint_authority(&payload.account, payload.allowed);
+    let symbol = native.symbol.clone();
+    drop(tokens);
+    persist_state(&state);
+
+    Json(ApiResponse {
+        status: "success".to_string(),
+        message: format!(
+            "{} {} mint {}",
+            payload.account,
+            if payload.allowed {
+                "may now"
+            } else {
+                "may no longer"
+            },
+            symbol
+        ),
+    })
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
+async fn set_max_supply(
+    State(state): State<AppState>,
+    Json(payload): Json<MaxSupplyRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut tokens = state.tokens.write().await;
+    let native = tokens.native_mut();
+    let updated = native.set_max_supply(payload.max_supply);
+    let symbol = native.symbol.clone();
+    drop(tokens);
+    updated?;
+    persist_state(&state);
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: match payload.max_supply {
+            Some(cap) => format!("{} supply capped at {}", symbol, cap),
+            None => format!("{} supply is uncapped", symbol),
+        },
+    }))
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
 async fn update_governance(
     State(state): State<AppState>,
-    headers: HeaderMap,
     Json(payload): Json<GovernanceRequest>,
-) -> Json<ApiResponse> {
-    if !authorize_admin(&headers, &state) {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "Unauthorized admin request".to_string(),
-        });
-    }
+) -> ApiResult<ApiResponse> {
     if payload.slash_percent == 0 || payload.slash_percent > 100 {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "slash_percent must be between 1 and 100".to_string(),
-        });
+        return Err(ApiError::bad_request(
+            "invalid_governance",
+            "slash_percent must be between 1 and 100",
+        ));
     }
     if payload.finality_depth == 0 || payload.finality_depth > 10_000 {
-        return Json(ApiResponse {
-            status: "error".to_string(),
-            message: "finality_depth must be between 1 and 10,000".to_string(),
-        });
+        return Err(ApiError::bad_request(
+            "invalid_governance",
+            "finality_depth must be between 1 and 10,000",
+        ));
     }
 
-    let mut governance = state.governance.lock().await;
+    let mut governance = state.governance.write().await;
     governance.slash_percent = payload.slash_percent;
     governance.finality_depth = payload.finality_depth;
     drop(governance);
-    let _ = persist_state(&state).await;
+    persist_state(&state);
 
-    Json(ApiResponse {
+    Ok(Json(ApiResponse {
         status: "success".to_string(),
         message: format!(
             "Updated slash_percent to {} and finality_depth to {}",
             payload.slash_percent, payload.finality_depth
         ),
-    })
+    }))
 }
 
+/// Requires [`Role::Admin`] (see `api_routes`).
 async fn submit_slash_evidence(
     State(state): State<AppState>,
-    headers: HeaderMap,
     Json(payload): Json<SlashEvidenceRequest>,
-) -> Json<SlashEvidenceResponse> {
-    if !authorize_admin(&headers, &state) {
-        return Json(SlashEvidenceResponse {
-            status: "error".to_string(),
-            message: "Unauthorized admin request".to_string(),
-            slashed_amount: 0,
-        });
-    }
-    let chain = state.chain.lock().await;
-    let evidence = match chain.evaluate_slash_evidence(payload.block_index) {
-        Ok(evidence) => evidence,
-        Err(message) => {
-            return Json(SlashEvidenceResponse {
-                status: "error".to_string(),
-                message,
-                slashed_amount: 0,
-            });
-        }
-    };
+) -> ApiResult<SlashEvidenceResponse> {
+    let chain = state.chain.read().await;
+    let evidence = chain.evaluate_slash_evidence(payload.block_index)?;
     drop(chain);
 
-    let mut stakers = state.stakers.lock().await;
-    let governance = state.governance.lock().await;
+    let mut stakers = state.stakers.write().await;
+    let governance = state.governance.read().await;
     let slashed_amount =
         pos::slash_staker_with_percent(&mut stakers, &evidence.validator, governance.slash_percent);
     drop(stakers);
     drop(governance);
 
-    let mut metrics = state.metrics.lock().await;
+    let mut metrics = state.metrics.write().await;
     metrics.slashes_submitted += 1;
     drop(metrics);
 
-    let mut evidence_log = state.slash_evidence.lock().await;
+    let mut evidence_log = state.slash_evidence.write().await;
     evidence_log.push(crate::persistence::SlashEvidence {
         block_index: payload.block_index,
-        reason: evidence.reason,
+        reason: evidence.reason.clone(),
         reporter: payload.reporter,
         timestamp: evidence.timestamp,
         slashed_amount,
     });
     drop(evidence_log);
-    let _ = persist_state(&state).await;
+    persist_state(&state);
+
+    if slashed_amount > 0 {
+        state.events.publish(ChainEvent::Slashed {
+            address: evidence.validator.clone(),
+            amount: slashed_amount,
+            reason: evidence.reason,
+        });
+    }
 
-    Json(SlashEvidenceResponse {
+    Ok(Json(SlashEvidenceResponse {
         status: "success".to_string(),
         message: format!(
             "Slashed validator {} by {}",
             evidence.validator, slashed_amount
         ),
         slashed_amount,
-    })
+    }))
 }
 
+/// Requires [`Role::Auditor`] (see `api_routes`).
 async fn list_slash_evidence(
     State(state): State<AppState>,
-    headers: HeaderMap,
 ) -> Json<Vec<crate::persistence::SlashEvidence>> {
-    if !authorize_admin(&headers, &state) {
-        return Json(Vec::new());
-    }
-    let evidence = state.slash_evidence.lock().await;
+    let evidence = state.slash_evidence.read().await;
     Json(evidence.clone())
 }
 
+// ===== ACCESS CONTROL ENDPOINTS =====
+
+async fn get_roles(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> Json<RolesResponse> {
+    let roles = state.roles.read().await;
+    Json(RolesResponse {
+        roles: roles.roles_of(&address),
+        address,
+    })
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
+async fn grant_role(
+    State(state): State<AppState>,
+    Json(payload): Json<RoleRequest>,
+) -> ApiResult<ApiResponse> {
+    if payload.address.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "invalid_address",
+            "Address cannot be empty",
+        ));
+    }
+
+    let mut roles = state.roles.write().await;
+    let granted = roles.grant(&payload.address, payload.role);
+    drop(roles);
+    persist_state(&state);
+
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: if granted {
+            format!("Granted {:?} to {}", payload.role, payload.address)
+        } else {
+            format!("{} already holds {:?}", payload.address, payload.role)
+        },
+    }))
+}
+
+/// Requires [`Role::Admin`] (see `api_routes`).
+async fn revoke_role(
+    State(state): State<AppState>,
+    Json(payload): Json<RoleRequest>,
+) -> ApiResult<ApiResponse> {
+    let mut roles = state.roles.write().await;
+    let result = roles.revoke(&payload.address, payload.role);
+    drop(roles);
+    let revoked = result.map_err(|message| ApiError::conflict("last_admin", message))?;
+
+    persist_state(&state);
+    Ok(Json(ApiResponse {
+        status: "success".to_string(),
+        message: if revoked {
+            format!("Revoked {:?} from {}", payload.role, payload.address)
+        } else {
+            format!("{} does not hold {:?}", payload.address, payload.role)
+        },
+    }))
+}
+
 async fn get_metrics(State(state): State<AppState>) -> Json<Metrics> {
-    let metrics = state.metrics.lock().await;
+    let metrics = state.metrics.read().await;
     Json(metrics.clone())
 }
 
 // ===== VALIDATION ENDPOINTS =====
 
 async fn validate_blockchain(State(state): State<AppState>) -> Json<ValidationResponse> {
-    let chain = state.chain.lock().await;
-    let mut stakers = state.stakers.lock().await;
+    let chain = state.chain.read().await;
+    let mut stakers = state.stakers.write().await;
     let (is_valid, slashed, details) = chain.validate_and_slash(&mut stakers);
+    let slashed: Vec<SlashEvent> = slashed
+        .into_iter()
+        .map(|(address, amount)| SlashEvent { address, amount })
+        .collect();
 
     drop(stakers);
-    let _ = persist_state(&state).await;
+    drop(chain);
+    persist_state(&state);
+    publish_slashes(
+        &state,
+        &slashed,
+        details.as_deref().unwrap_or("Chain validation failed"),
+    );
 
     Json(ValidationResponse {
         is_valid,
@@ -1198,10 +3451,7 @@ async fn validate_blockchain(State(state): State<AppState>) -> Json<ValidationRe
         } else {
             details
         },
-        slashed: slashed
-            .into_iter()
-            .map(|(address, amount)| SlashEvent { address, amount })
-            .collect(),
+        slashed,
     })
 }
 
@@ -1209,8 +3459,8 @@ async fn validate_block(
     State(state): State<AppState>,
     Path(index): Path<usize>,
 ) -> Json<ValidationResponse> {
-    let chain = state.chain.lock().await;
-    let mut stakers = state.stakers.lock().await;
+    let chain = state.chain.read().await;
+    let mut stakers = state.stakers.write().await;
 
     if index >= chain.blocks.len() {
         return Json(ValidationResponse {
@@ -1244,10 +3494,12 @@ async fn validate_block(
         error = Some("Missing validator public key".to_string());
     } else if current_block.validator_signature.is_none() {
         error = Some("Missing validator signature".to_string());
-    } else if current_block.staker_snapshot.is_none() || current_block.staker_set_hash.is_none() {
-        error = Some("Missing staker snapshot data".to_string());
-    } else {
-        let staker_snapshot = current_block.staker_snapshot.as_ref().unwrap();
+    } else if let (Some(public_key), Some(signature), Some(staker_snapshot), Some(_)) = (
+        &current_block.validator_public_key,
+        &current_block.validator_signature,
+        &current_block.staker_snapshot,
+        &current_block.staker_set_hash,
+    ) {
         let staker_hash = pos::staker_set_hash(staker_snapshot);
         if Some(staker_hash) != current_block.staker_set_hash {
             error = Some("Staker set hash mismatch".to_string());
@@ -1265,11 +3517,7 @@ async fn validate_block(
                     }
                 }
                 error = Some("Validator does not match PoS selection".to_string());
-            } else if !pos::verify_block_signature(
-                &current_block.hash,
-                current_block.validator_public_key.as_ref().unwrap(),
-                current_block.validator_signature.as_ref().unwrap(),
-            ) {
+            } else if !pos::verify_block_signature(&current_block.hash, public_key, signature) {
                 if let Some(validator) = &current_block.validator {
                     let amount = pos::slash_staker(&mut stakers, validator);
                     if amount > 0 {
@@ -1293,12 +3541,18 @@ async fn validate_block(
                 error = Some("Block failed PoW validation".to_string());
             }
         }
+    } else {
+        error = Some("Missing staker snapshot data".to_string());
     }
 
     let is_valid = error.is_none();
 
     drop(stakers);
-    let _ = persist_state(&state).await;
+    drop(chain);
+    persist_state(&state);
+    if let Some(reason) = &error {
+        publish_slashes(&state, &slashed, reason);
+    }
 
     Json(ValidationResponse {
         is_valid,
@@ -1318,7 +3572,7 @@ async fn validate_block(
 
 // Tutorial compatibility endpoint
 async fn validate_chain(State(state): State<AppState>) -> Json<ApiResponse> {
-    let chain = state.chain.lock().await;
+    let chain = state.chain.read().await;
     let is_valid = chain.is_valid();
 
     Json(ApiResponse {
@@ -1333,8 +3587,219 @@ async fn validate_chain(State(state): State<AppState>) -> Json<ApiResponse> {
 
 // ===== TRANSACTION ENDPOINTS =====
 
-async fn get_pending_transactions(State(state): State<AppState>) -> Json<Vec<String>> {
-    let pending = state.pending_transactions.lock().await;
-    let transaction_strings: Vec<String> = pending.iter().map(|tx| format!("{:?}", tx)).collect();
-    Json(transaction_strings)
+async fn get_pending_transactions(State(state): State<AppState>) -> Json<Vec<Transaction>> {
+    let pending = state.pending_transactions.read().await;
+    Json(pending.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::fungible::Token;
+    use std::time::{Duration, Instant};
+
+    const CLIENTS: usize = 32;
+    const LOAD_DURATION: Duration = Duration::from_secs(5);
+
+    fn test_state(native: Token, auth_manager: AuthManager) -> AppState {
+        AppState {
+            chain: Arc::default(),
+            tokens: Arc::new(RwLock::new(TokenRegistry::new(native))),
+            contracts: Arc::default(),
+            pending_transactions: Arc::default(),
+            auth_manager: Arc::new(RwLock::new(auth_manager)),
+            stakers: Arc::default(),
+            peers: Arc::default(),
+            governance: Arc::default(),
+            slash_evidence: Arc::default(),
+            roles: Arc::default(),
+            issuers: Arc::default(),
+            receipts: Arc::default(),
+            nfts: Arc::default(),
+            mining: Arc::default(),
+            metrics: Arc::default(),
+            events: EventBus::new(),
+            persist: PersistSignal::default(),
+            p2p_token: None,
+            p2p_service: Arc::new(P2PService::new("test-node".to_string(), None).unwrap()),
+        }
+    }
+
+    #[test]
+    fn anchors_match_transaction_ids_exactly() {
+        let mut chain = Blockchain::default();
+        let mut tx = Transaction::new(
+            Some("0xissuer".to_string()),
+            "0xholder".to_string(),
+            0,
+            TransactionType::Certificate,
+        )
+        .with_reference("CERT-10");
+        tx.id = "tx-10".to_string();
+        let mut block = chain.block_template(vec![tx.into()], None, None, None, None);
+        assert!(block
+            .mine(&std::sync::atomic::AtomicBool::new(false))
+            .unwrap());
+        chain.add_mined_block(block);
+
+        let anchor = |transaction_id: &str| BlockAnchor {
+            block_index: 1,
+            block_hash: chain.blocks[1].hash.clone(),
+            transaction_id: transaction_id.to_string(),
+        };
+        assert!(is_anchored(&chain, &anchor("tx-10"), "CERT-10"));
+        // A prefix of the id, or the id of another certificate's transaction
+        assert!(!is_anchored(&chain, &anchor("tx-1"), "CERT-10"));
+        assert!(!is_anchored(&chain, &anchor("tx-10"), "CERT-1"));
+        assert!(!is_anchored(&chain, &anchor("CERT-10"), "CERT-10"));
+    }
+
+    #[tokio::test]
+    async fn peer_blocks_seal_receipts() {
+        let state = test_state(
+            Token::new("Metacation Token", "MCT", 1000, "admin"),
+            AuthManager::new(),
+        );
+        let tx = Transaction::new(
+            Some("0xalice".to_string()),
+            "0xbob".to_string(),
+            5,
+            TransactionType::Transfer,
+        );
+        state.pending_transactions.write().await.push(tx.clone());
+        state
+            .receipts
+            .write()
+            .await
+            .record(Receipt::new(&tx, Vec::new()));
+
+        let secret_key = secp256k1::SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let public_key = hex::encode(
+            secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::new(), &secret_key)
+                .serialize_uncompressed(),
+        );
+        let private_key = hex::encode(secret_key.secret_bytes());
+        let stakers = vec![Staker {
+            address: "validator-1".to_string(),
+            stake: 10,
+            public_key: Some(public_key.clone()),
+            private_key: None,
+        }];
+        let mut block = state.chain.read().await.block_template(
+            vec![tx.clone().into()],
+            Some("validator-1".to_string()),
+            Some(public_key),
+            Some(pos::staker_set_hash(&stakers)),
+            Some(stakers),
+        );
+        assert!(block
+            .mine(&std::sync::atomic::AtomicBool::new(false))
+            .unwrap());
+        block.validator_signature = Some(pos::sign_block_hash(&block.hash, &private_key).unwrap());
+        let block_hash = block.hash.clone();
+
+        let Json(response) = receive_block(State(state.clone()), HeaderMap::new(), Json(block))
+            .await
+            .unwrap();
+        assert_eq!(response.message, "Block accepted");
+
+        let receipts = state.receipts.read().await;
+        let receipt = receipts.get(&tx.id).unwrap();
+        assert_eq!(receipt.block_index, Some(1));
+        assert_eq!(receipt.block_hash.as_deref(), Some(block_hash.as_str()));
+        assert!(state.pending_transactions.read().await.is_empty());
+    }
+
+    /// Mixed read/write load against a node served over HTTP, with state
+    /// persisted to a temporary directory. Writes the request throughput to
+    /// `bench/results/load_test.json`; run it with
+    /// `cargo test --release -- --ignored load_test`.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "load test; run explicitly"]
+    async fn load_test_mixed_traffic() {
+        let wallets: Vec<String> = (0..CLIENTS).map(|i| format!("0xwallet{}", i)).collect();
+        let mut native = Token::new("Metacation Token", "MCT", 1_000_000, "admin");
+        for wallet in &wallets {
+            native.transfer("admin", wallet, 10_000).unwrap();
+        }
+        let mut auth_manager = AuthManager::new();
+        let sessions: Vec<String> = wallets
+            .iter()
+            .map(|wallet| auth_manager.create_session(wallet).unwrap().token)
+            .collect();
+
+        let state = test_state(native, auth_manager);
+        let state_dir = std::env::temp_dir().join(format!("hikmalayer-{}", uuid::Uuid::new_v4()));
+        let (stop_persister, persister_shutdown) = oneshot::channel();
+        let persister = tokio::spawn(run_persister(
+            state.clone(),
+            state_dir.join("state.json"),
+            persister_shutdown,
+        ));
+
+        let app = api_routes(state.clone()).with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let started = Instant::now();
+        let workers: Vec<_> = (0..CLIENTS)
+            .map(|i| {
+                let client = client.clone();
+                let base = base.clone();
+                let from = wallets[i].clone();
+                let to = wallets[(i + 1) % CLIENTS].clone();
+                let session = sessions[i].clone();
+                tokio::spawn(async move {
+                    let mut completed = 0u64;
+                    while started.elapsed() < LOAD_DURATION {
+                        // Four reads for every write
+                        let response = match completed % 5 {
+                            0 => client
+                                .post(format!("{}/tokens/transfer", base))
+                                .bearer_auth(&session)
+                                .json(&serde_json::json!({ "from": from, "to": to, "amount": 1 }))
+                                .send(),
+                            1 | 3 => client
+                                .get(format!("{}/tokens/balance/{}", base, from))
+                                .send(),
+                            _ => client.get(format!("{}/blockchain/stats", base)).send(),
+                        };
+                        assert!(response.await.unwrap().status().is_success());
+                        completed += 1;
+                    }
+                    completed
+                })
+            })
+            .collect();
+
+        let mut total = 0u64;
+        for worker in workers {
+            total += worker.await.unwrap();
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+        let report = serde_json::json!({
+            "clients": CLIENTS,
+            "seconds": elapsed,
+            "requests": total,
+            "requests_per_second": total as f64 / elapsed,
+        });
+        let report_path =
+            FsPath::new(env!("CARGO_MANIFEST_DIR")).join("bench/results/load_test.json");
+        std::fs::create_dir_all(report_path.parent().unwrap()).unwrap();
+        std::fs::write(&report_path, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+
+        stop_persister.send(()).unwrap();
+        persister.await.unwrap();
+        // The shutdown flush saves everything written during the run
+        let saved = crate::persistence::load_state(&state_dir.join("state.json"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            saved.pending_transactions.len(),
+            state.pending_transactions.read().await.len()
+        );
+        let _ = std::fs::remove_dir_all(&state_dir);
+    }
 }
@@ -0,0 +1,38 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+
+use super::routes::AppState;
+use crate::events::EventFilter;
+
+pub fn stream_routes() -> Router<AppState> {
+    Router::new().route("/events/stream", get(stream_events))
+}
+
+/// Server-Sent Events feed of chain events. Each SSE message uses the event
+/// type as its name and the JSON-encoded event as its data. Subscribers that
+/// fall too far behind silently skip the missed events.
+async fn stream_events(
+    State(state): State<AppState>,
+    Query(filter): Query<EventFilter>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).filter_map(move |item| {
+        let event = item.ok()?;
+        if !filter.matches(&event) {
+            return None;
+        }
+        Event::default()
+            .event(event.event_type())
+            .json_data(&event)
+            .ok()
+            .map(Ok)
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -1,32 +1,106 @@
 // src/auth/middleware.rs
 use axum::{
     extract::{Request, State},
-    http::{HeaderMap, StatusCode},
+    http::HeaderMap,
     middleware::Next,
     response::Response,
 };
 
+use super::rbac::Role;
+use crate::api::{error::ApiError, routes::AppState};
+
+/// Wallet address of the session that made the request, inserted into request
+/// extensions by [`auth_middleware`].
+#[derive(Debug, Clone)]
+pub struct AuthenticatedAddress(pub String);
+
+impl AuthenticatedAddress {
+    /// Wallet addresses are compared case-insensitively (EIP-55 checksums vary in case).
+    pub fn matches(&self, address: &str) -> bool {
+        self.0.eq_ignore_ascii_case(address)
+    }
+}
+
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+}
+
+/// Rejection for a missing, expired or revoked session token.
+pub fn invalid_session() -> ApiError {
+    ApiError::unauthorized("A valid session token is required")
+}
+
+async fn session_address(state: &AppState, headers: &HeaderMap) -> Result<String, ApiError> {
+    let token = bearer_token(headers).ok_or_else(invalid_session)?;
+    let auth_manager = state.auth_manager.read().await;
+    auth_manager
+        .verify_session(token)
+        .ok_or_else(invalid_session)
+}
+
 pub async fn auth_middleware(
-    headers: HeaderMap,
-    State(_state): State<crate::api::routes::AppState>,
-    request: Request,
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let address = session_address(&state, request.headers()).await?;
+
+    request
+        .extensions_mut()
+        .insert(AuthenticatedAddress(address));
+    Ok(next.run(request).await)
+}
+
+/// State for [`role_middleware`]: the role a group of routes requires.
+#[derive(Clone)]
+pub struct RoleGuard {
+    pub state: AppState,
+    pub role: Role,
+}
+
+/// Like [`auth_middleware`], but also rejects sessions whose address does not
+/// hold the guarded role with `403 Forbidden`.
+pub async fn role_middleware(
+    State(guard): State<RoleGuard>,
+    mut request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    // Extract Authorization header
-    if let Some(auth_header) = headers.get("authorization") {
-        if let Ok(auth_str) = auth_header.to_str() {
-            if auth_str.starts_with("Bearer ") {
-                let token = &auth_str[7..];
-
-                // Here you'd verify the token against your AuthManager
-                // For now, we'll just check if it's not empty
-                if !token.is_empty() {
-                    // Add user info to request extensions if needed
-                    return Ok(next.run(request).await);
-                }
-            }
-        }
+) -> Result<Response, ApiError> {
+    let address = session_address(&guard.state, request.headers()).await?;
+
+    let allowed = {
+        let roles = guard.state.roles.read().await;
+        roles.has_role(&address, guard.role)
+    };
+    if !allowed {
+        return Err(ApiError::forbidden(
+            "missing_role",
+            format!("This endpoint requires the {:?} role", guard.role),
+        ));
     }
 
-    Err(StatusCode::UNAUTHORIZED)
+    request
+        .extensions_mut()
+        .insert(AuthenticatedAddress(address));
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert!(bearer_token(&headers).is_none());
+
+        headers.insert("authorization", "Bearer abc".parse().unwrap());
+        assert_eq!(bearer_token(&headers), Some("abc"));
+
+        headers.insert("authorization", "Bearer ".parse().unwrap());
+        assert!(bearer_token(&headers).is_none());
+    }
 }
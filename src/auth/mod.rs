@@ -1,14 +1,59 @@
-// src/auth/mod.rs
 pub mod middleware;
+pub mod rbac;
 pub mod routes;
 pub mod signature;
+pub mod siwe;
 
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use siwe::{SiweConfig, SiweMessage};
+
+const DEFAULT_SESSION_TTL_SECONDS: i64 = 3600;
+const DEFAULT_NONCE_TTL_SECONDS: i64 = 300;
+
+struct PendingNonce {
+    nonce: String,
+    expires_at: DateTime<Utc>,
+}
+
+struct Session {
+    address: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Claims carried by stateless session tokens.
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionClaims {
+    sub: String,
+    jti: String,
+    iat: i64,
+    exp: i64,
+}
+
+/// Signing keys for stateless sessions. Nodes that share the key accept each
+/// other's tokens, and tokens survive restarts.
+struct JwtKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+}
+
+pub struct IssuedSession {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 pub struct AuthManager {
-    pub sessions: HashMap<String, String>,
-    pub nonces: HashMap<String, String>,
+    sessions: HashMap<String, Session>,
+    nonces: HashMap<String, PendingNonce>,
+    /// Revoked JWT ids, kept until the token would have expired anyway.
+    revoked_jwt_ids: HashMap<String, DateTime<Utc>>,
+    jwt: Option<JwtKeys>,
+    session_ttl: Duration,
+    nonce_ttl: Duration,
+    siwe: SiweConfig,
 }
 
 impl AuthManager {
@@ -16,36 +61,184 @@ impl AuthManager {
         Self {
             sessions: HashMap::new(),
             nonces: HashMap::new(),
+            revoked_jwt_ids: HashMap::new(),
+            jwt: None,
+            session_ttl: Duration::seconds(DEFAULT_SESSION_TTL_SECONDS),
+            nonce_ttl: Duration::seconds(DEFAULT_NONCE_TTL_SECONDS),
+            siwe: SiweConfig::default(),
         }
     }
 
+    /// Issue stateless HS256 session tokens signed with the node key instead of
+    /// keeping sessions in memory.
+    pub fn with_jwt_secret(mut self, secret: &[u8]) -> Self {
+        self.jwt = Some(JwtKeys {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+        });
+        self
+    }
+
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    pub fn with_nonce_ttl(mut self, ttl: Duration) -> Self {
+        self.nonce_ttl = ttl;
+        self
+    }
+
+    pub fn with_siwe_config(mut self, config: SiweConfig) -> Self {
+        self.siwe = config;
+        self
+    }
+
+    /// Restores JWT revocations saved by [`AuthManager::revoked_sessions`].
+    pub fn with_revoked_sessions(mut self, revoked: HashMap<String, DateTime<Utc>>) -> Self {
+        self.revoked_jwt_ids = revoked;
+        self
+    }
+
+    /// Revoked JWT ids and when each token expires, for persisting across
+    /// restarts. Empty unless JWT sessions are enabled.
+    pub fn revoked_sessions(&self) -> HashMap<String, DateTime<Utc>> {
+        let now = Utc::now();
+        self.revoked_jwt_ids
+            .iter()
+            .filter(|(_, expires_at)| **expires_at > now)
+            .map(|(jti, expires_at)| (jti.clone(), *expires_at))
+            .collect()
+    }
+
+    /// Parses an EIP-4361 sign-in message and checks it was issued for this
+    /// service, the claimed address and the outstanding nonce.
+    pub fn check_sign_in_message(
+        &self,
+        message: &str,
+        address: &str,
+        nonce: &str,
+    ) -> Result<SiweMessage, String> {
+        let parsed: SiweMessage = message.parse()?;
+        parsed.validate(&self.siwe, address, nonce, self.nonce_ttl, Utc::now())?;
+        Ok(parsed)
+    }
+
     pub fn generate_nonce(&mut self, address: &str) -> String {
+        self.prune_expired();
         let nonce = uuid::Uuid::new_v4().to_string();
-        self.nonces.insert(address.to_string(), nonce.clone());
+        self.nonces.insert(
+            address.to_lowercase(),
+            PendingNonce {
+                nonce: nonce.clone(),
+                expires_at: Utc::now() + self.nonce_ttl,
+            },
+        );
         nonce
     }
 
     pub fn verify_nonce(&self, address: &str, nonce: &str) -> bool {
         self.nonces
-            .get(address)
-            .map_or(false, |stored_nonce| stored_nonce == nonce)
+            .get(&address.to_lowercase())
+            .is_some_and(|pending| pending.nonce == nonce && pending.expires_at > Utc::now())
     }
 
-    pub fn create_session(&mut self, address: &str) -> String {
-        let token = format!("{}_{}", uuid::Uuid::new_v4().to_string(), address);
-        self.sessions.insert(token.clone(), address.to_string());
+    pub fn create_session(&mut self, address: &str) -> Result<IssuedSession, String> {
         // Remove the nonce after successful authentication
-        self.nonces.remove(address);
-        token
+        self.nonces.remove(&address.to_lowercase());
+        self.issue_session(address)
     }
 
     pub fn verify_session(&self, token: &str) -> Option<String> {
-        self.sessions.get(token).cloned()
+        match &self.jwt {
+            Some(keys) => {
+                let claims = decode_claims(keys, token)?;
+                if self.revoked_jwt_ids.contains_key(&claims.jti) {
+                    return None;
+                }
+                Some(claims.sub)
+            }
+            None => self
+                .sessions
+                .get(token)
+                .filter(|session| session.expires_at > Utc::now())
+                .map(|session| session.address.clone()),
+        }
     }
 
-    pub fn revoke_session(&mut self, token: &str) {
-        self.sessions.remove(token);
+    /// Exchanges a valid session token for a fresh one and revokes the old token.
+    pub fn refresh_session(&mut self, token: &str) -> Option<Result<IssuedSession, String>> {
+        let address = self.verify_session(token)?;
+        self.revoke_session(token);
+        Some(self.issue_session(&address))
     }
+
+    /// Returns `true` if the token was a live session.
+    pub fn revoke_session(&mut self, token: &str) -> bool {
+        match &self.jwt {
+            Some(keys) => {
+                let Some(claims) = decode_claims(keys, token) else {
+                    return false;
+                };
+                let expires_at = DateTime::from_timestamp(claims.exp, 0).unwrap_or_else(Utc::now);
+                self.revoked_jwt_ids
+                    .insert(claims.jti, expires_at)
+                    .is_none()
+            }
+            None => self
+                .sessions
+                .remove(token)
+                .is_some_and(|session| session.expires_at > Utc::now()),
+        }
+    }
+
+    fn issue_session(&mut self, address: &str) -> Result<IssuedSession, String> {
+        self.prune_expired();
+        let issued_at = Utc::now();
+        let expires_at = issued_at + self.session_ttl;
+
+        let token = match &self.jwt {
+            Some(keys) => {
+                let claims = SessionClaims {
+                    sub: address.to_string(),
+                    jti: uuid::Uuid::new_v4().to_string(),
+                    iat: issued_at.timestamp(),
+                    exp: expires_at.timestamp(),
+                };
+                encode(&Header::new(Algorithm::HS256), &claims, &keys.encoding)
+                    .map_err(|err| format!("Failed to sign session token: {}", err))?
+            }
+            None => {
+                let token = format!("{}_{}", uuid::Uuid::new_v4(), address);
+                self.sessions.insert(
+                    token.clone(),
+                    Session {
+                        address: address.to_string(),
+                        expires_at,
+                    },
+                );
+                token
+            }
+        };
+
+        Ok(IssuedSession { token, expires_at })
+    }
+
+    fn prune_expired(&mut self) {
+        let now = Utc::now();
+        self.sessions.retain(|_, session| session.expires_at > now);
+        self.nonces.retain(|_, pending| pending.expires_at > now);
+        self.revoked_jwt_ids
+            .retain(|_, expires_at| *expires_at > now);
+    }
+}
+
+fn decode_claims(keys: &JwtKeys, token: &str) -> Option<SessionClaims> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.leeway = 0;
+    decode::<SessionClaims>(token, &keys.decoding, &validation)
+        .ok()
+        .map(|data| data.claims)
 }
 
 #[derive(Deserialize)]
@@ -70,6 +263,7 @@ pub struct VerifyRequest {
 pub struct VerifyResponse {
     pub token: String,
     pub address: String,
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Serialize)]
@@ -77,3 +271,57 @@ pub struct AuthResponse {
     pub status: String,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_expire_and_revoke() {
+        let mut auth = AuthManager::new();
+        let session = auth.create_session("0xabc").unwrap();
+        assert_eq!(
+            auth.verify_session(&session.token).as_deref(),
+            Some("0xabc")
+        );
+        assert!(auth.revoke_session(&session.token));
+        assert!(auth.verify_session(&session.token).is_none());
+
+        let mut expired = AuthManager::new().with_session_ttl(Duration::seconds(-1));
+        let session = expired.create_session("0xabc").unwrap();
+        assert!(expired.verify_session(&session.token).is_none());
+    }
+
+    #[test]
+    fn nonces_expire() {
+        let mut auth = AuthManager::new().with_nonce_ttl(Duration::seconds(-1));
+        let nonce = auth.generate_nonce("0xabc");
+        assert!(!auth.verify_nonce("0xabc", &nonce));
+    }
+
+    #[test]
+    fn jwt_sessions_refresh_and_revoke() {
+        let mut auth = AuthManager::new().with_jwt_secret(b"node-key");
+        let session = auth.create_session("0xabc").unwrap();
+
+        let other_node = AuthManager::new().with_jwt_secret(b"node-key");
+        assert_eq!(
+            other_node.verify_session(&session.token).as_deref(),
+            Some("0xabc")
+        );
+
+        let refreshed = auth.refresh_session(&session.token).unwrap().unwrap();
+        assert!(auth.verify_session(&session.token).is_none());
+        assert_eq!(
+            auth.verify_session(&refreshed.token).as_deref(),
+            Some("0xabc")
+        );
+
+        // Revocations outlive a restart once restored from the snapshot
+        let restarted = AuthManager::new()
+            .with_jwt_secret(b"node-key")
+            .with_revoked_sessions(auth.revoked_sessions());
+        assert!(restarted.verify_session(&session.token).is_none());
+        assert!(restarted.verify_session(&refreshed.token).is_some());
+    }
+}
@@ -0,0 +1,91 @@
+// src/auth/rbac.rs
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Grants and revokes roles and changes governance settings. Implies every other role.
+    Admin,
+    CertificateIssuer,
+    ValidatorOperator,
+    /// Read access to slashing evidence and other operator-only records.
+    Auditor,
+}
+
+/// Role assignments keyed by lowercased wallet address.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleRegistry {
+    assignments: HashMap<String, BTreeSet<Role>>,
+}
+
+impl RoleRegistry {
+    pub fn has_role(&self, address: &str, role: Role) -> bool {
+        self.assignments
+            .get(&address.to_lowercase())
+            .is_some_and(|roles| roles.contains(&role) || roles.contains(&Role::Admin))
+    }
+
+    /// Returns `false` if the address already held the role.
+    pub fn grant(&mut self, address: &str, role: Role) -> bool {
+        self.assignments
+            .entry(address.to_lowercase())
+            .or_default()
+            .insert(role)
+    }
+
+    /// Returns `false` if the address did not hold the role. The last admin
+    /// cannot be revoked, so the registry always stays manageable.
+    pub fn revoke(&mut self, address: &str, role: Role) -> Result<bool, String> {
+        let key = address.to_lowercase();
+        if role == Role::Admin && self.holders(Role::Admin) == vec![key.clone()] {
+            return Err("Cannot revoke the last admin".to_string());
+        }
+        let Some(roles) = self.assignments.get_mut(&key) else {
+            return Ok(false);
+        };
+        let removed = roles.remove(&role);
+        if roles.is_empty() {
+            self.assignments.remove(&key);
+        }
+        Ok(removed)
+    }
+
+    pub fn roles_of(&self, address: &str) -> Vec<Role> {
+        self.assignments
+            .get(&address.to_lowercase())
+            .map(|roles| roles.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn holders(&self, role: Role) -> Vec<String> {
+        let mut holders: Vec<String> = self
+            .assignments
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(address, _)| address.clone())
+            .collect();
+        holders.sort();
+        holders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admin_implies_other_roles_and_cannot_be_orphaned() {
+        let mut roles = RoleRegistry::default();
+        assert!(roles.grant("0xAdmin", Role::Admin));
+        assert!(roles.grant("0xIssuer", Role::CertificateIssuer));
+
+        assert!(roles.has_role("0xadmin", Role::CertificateIssuer));
+        assert!(roles.has_role("0xISSUER", Role::CertificateIssuer));
+        assert!(!roles.has_role("0xissuer", Role::Auditor));
+
+        assert!(roles.revoke("0xadmin", Role::Admin).is_err());
+        assert_eq!(roles.revoke("0xissuer", Role::CertificateIssuer), Ok(true));
+        assert!(roles.roles_of("0xissuer").is_empty());
+    }
+}
@@ -1,29 +1,35 @@
 // src/auth/routes.rs
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
     routing::{delete, post},
     Router,
 };
 
 use super::{
-    signature::recover_address_from_signature, AuthResponse, NonceRequest, NonceResponse,
-    VerifyRequest, VerifyResponse,
+    middleware::{bearer_token, invalid_session},
+    signature::recover_address_from_signature,
+    AuthResponse, NonceRequest, NonceResponse, VerifyRequest, VerifyResponse,
+};
+use crate::api::{
+    error::{ApiError, ApiResult},
+    routes::persist_state,
 };
 
 pub fn auth_routes() -> Router<crate::api::routes::AppState> {
     Router::new()
         .route("/auth/nonce", post(generate_nonce))
         .route("/auth/verify", post(verify_signature))
+        .route("/auth/refresh", post(refresh_session))
         .route("/auth/logout", delete(logout))
 }
 
 async fn generate_nonce(
     State(state): State<crate::api::routes::AppState>,
     Json(payload): Json<NonceRequest>,
-) -> Result<Json<NonceResponse>, StatusCode> {
-    let mut auth_manager = state.auth_manager.lock().await;
+) -> ApiResult<NonceResponse> {
+    let mut auth_manager = state.auth_manager.write().await;
     let nonce = auth_manager.generate_nonce(&payload.address);
 
     Ok(Json(NonceResponse { nonce }))
@@ -32,12 +38,22 @@ async fn generate_nonce(
 async fn verify_signature(
     State(state): State<crate::api::routes::AppState>,
     Json(payload): Json<VerifyRequest>,
-) -> Result<Json<VerifyResponse>, StatusCode> {
-    let mut auth_manager = state.auth_manager.lock().await;
+) -> ApiResult<VerifyResponse> {
+    let mut auth_manager = state.auth_manager.write().await;
 
     // Verify the nonce
     if !auth_manager.verify_nonce(&payload.address, &payload.nonce) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(
+            "invalid_nonce",
+            "Nonce is unknown, expired or already used",
+        ));
+    }
+
+    // The signed message must be a SIWE message bound to this service and nonce
+    if let Err(message) =
+        auth_manager.check_sign_in_message(&payload.message, &payload.address, &payload.nonce)
+    {
+        return Err(ApiError::bad_request("invalid_sign_in_message", message));
     }
 
     // Verify the signature
@@ -46,25 +62,64 @@ async fn verify_signature(
             // Check if recovered address matches the claimed address
             if recovered_address.to_lowercase() == payload.address.to_lowercase() {
                 // Create session token
-                let token = auth_manager.create_session(&payload.address);
+                let session = auth_manager
+                    .create_session(&payload.address)
+                    .map_err(|err| ApiError::internal(err.to_string()))?;
 
                 Ok(Json(VerifyResponse {
-                    token,
+                    token: session.token,
                     address: payload.address,
+                    expires_at: session.expires_at,
                 }))
             } else {
-                Err(StatusCode::UNAUTHORIZED)
+                Err(ApiError::new(
+                    StatusCode::UNAUTHORIZED,
+                    "signature_mismatch",
+                    "Signature was not made by the claimed address",
+                ))
             }
         }
-        Err(_) => Err(StatusCode::BAD_REQUEST),
+        Err(err) => Err(ApiError::bad_request("invalid_signature", err.to_string())),
     }
 }
 
-async fn logout(State(_state): State<crate::api::routes::AppState>) -> Json<AuthResponse> {
-    // In a full implementation, you'd get the token from the Authorization header
-    // and revoke it. For now, just return success.
-    Json(AuthResponse {
+async fn refresh_session(
+    State(state): State<crate::api::routes::AppState>,
+    headers: HeaderMap,
+) -> ApiResult<VerifyResponse> {
+    let token = bearer_token(&headers).ok_or_else(invalid_session)?;
+    let mut auth_manager = state.auth_manager.write().await;
+    let address = auth_manager
+        .verify_session(token)
+        .ok_or_else(invalid_session)?;
+    let session = auth_manager
+        .refresh_session(token)
+        .ok_or_else(invalid_session)?
+        .map_err(|err| ApiError::internal(err.to_string()))?;
+    drop(auth_manager);
+    persist_state(&state);
+
+    Ok(Json(VerifyResponse {
+        token: session.token,
+        address,
+        expires_at: session.expires_at,
+    }))
+}
+
+async fn logout(
+    State(state): State<crate::api::routes::AppState>,
+    headers: HeaderMap,
+) -> ApiResult<AuthResponse> {
+    let token = bearer_token(&headers).ok_or_else(invalid_session)?;
+    let mut auth_manager = state.auth_manager.write().await;
+    if !auth_manager.revoke_session(token) {
+        return Err(invalid_session());
+    }
+    drop(auth_manager);
+    persist_state(&state);
+
+    Ok(Json(AuthResponse {
         status: "success".to_string(),
         message: "Successfully logged out".to_string(),
-    })
+    }))
 }
@@ -36,8 +36,8 @@ pub fn recover_address_from_signature(
     sig_bytes[32..].copy_from_slice(s);
     let _signature = Signature::from_compact(&sig_bytes)?;
 
-    // Recovery ID (v - 27 for Ethereum)
-    let recovery_id = RecoveryId::from_i32((v as i32) - 27)?;
+    // Recovery ID (v - 27 for Ethereum; some hardware wallets emit 0/1)
+    let recovery_id = RecoveryId::from_i32(if v >= 27 { v as i32 - 27 } else { v as i32 })?;
 
     // Recover public key - Updated API: recovery_id is now part of RecoverableSignature
     let recoverable_sig =
@@ -53,6 +53,7 @@ pub fn recover_address_from_signature(
 }
 
 /// Verify that a signature was created by the claimed address
+#[allow(dead_code)]
 pub fn verify_signature(address: &str, message: &str, signature: &str) -> bool {
     match recover_address_from_signature(message, signature) {
         Ok(recovered_address) => recovered_address.to_lowercase() == address.to_lowercase(),
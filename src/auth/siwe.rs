@@ -0,0 +1,283 @@
+// src/auth/siwe.rs
+use chrono::{DateTime, Duration, Utc};
+use std::str::FromStr;
+
+const PREAMBLE_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+const SUPPORTED_VERSION: &str = "1";
+const MAX_ISSUED_AT_SKEW_SECONDS: i64 = 60;
+
+/// What this node expects to find in a Sign-In with Ethereum message.
+#[derive(Debug, Clone)]
+pub struct SiweConfig {
+    /// RFC 3986 authority the user is signing in to, e.g. `app.example.com`.
+    pub domain: String,
+    /// When set, the message URI must start with this value.
+    pub uri: Option<String>,
+    pub chain_id: u64,
+}
+
+impl Default for SiweConfig {
+    fn default() -> Self {
+        Self {
+            domain: "localhost:5173".to_string(),
+            uri: None,
+            chain_id: 1,
+        }
+    }
+}
+
+/// A parsed EIP-4361 message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|value| value.with_timezone(&Utc))
+        .map_err(|_| format!("Invalid {} timestamp", field))
+}
+
+pub fn is_ethereum_address(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl FromStr for SiweMessage {
+    type Err = String;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let mut lines = message.lines().peekable();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE_SUFFIX))
+            .ok_or("Missing SIWE preamble")?;
+        // The preamble may carry an optional scheme, e.g. `https://example.com`.
+        let domain = domain
+            .split_once("://")
+            .map_or(domain, |(_, authority)| authority)
+            .to_string();
+
+        let address = lines.next().ok_or("Missing address")?.trim().to_string();
+        if !is_ethereum_address(&address) {
+            return Err("Invalid address line".to_string());
+        }
+
+        while lines.peek().is_some_and(|line| line.is_empty()) {
+            lines.next();
+        }
+        let statement = match lines.peek() {
+            Some(line) if !line.starts_with("URI: ") => {
+                let statement = line.to_string();
+                lines.next();
+                while lines.peek().is_some_and(|line| line.is_empty()) {
+                    lines.next();
+                }
+                Some(statement)
+            }
+            _ => None,
+        };
+
+        let mut uri = None;
+        let mut version = None;
+        let mut chain_id = None;
+        let mut nonce = None;
+        let mut issued_at = None;
+        let mut expiration_time = None;
+        let mut not_before = None;
+        let mut request_id = None;
+        let mut resources = Vec::new();
+
+        while let Some(line) = lines.next() {
+            if line == "Resources:" {
+                for resource in lines.by_ref() {
+                    let resource = resource
+                        .strip_prefix("- ")
+                        .ok_or("Invalid resource entry")?;
+                    resources.push(resource.to_string());
+                }
+                break;
+            }
+
+            let (field, value) = line
+                .split_once(": ")
+                .ok_or_else(|| format!("Unexpected line in SIWE message: {}", line))?;
+            match field {
+                "URI" => uri = Some(value.to_string()),
+                "Version" => version = Some(value.to_string()),
+                "Chain ID" => {
+                    chain_id = Some(value.parse::<u64>().map_err(|_| "Invalid chain ID")?)
+                }
+                "Nonce" => nonce = Some(value.to_string()),
+                "Issued At" => issued_at = Some(parse_timestamp("issued-at", value)?),
+                "Expiration Time" => {
+                    expiration_time = Some(parse_timestamp("expiration-time", value)?)
+                }
+                "Not Before" => not_before = Some(parse_timestamp("not-before", value)?),
+                "Request ID" => request_id = Some(value.to_string()),
+                _ => return Err(format!("Unknown SIWE field: {}", field)),
+            }
+        }
+
+        let nonce = nonce.ok_or("Missing nonce")?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err("Invalid nonce".to_string());
+        }
+
+        Ok(SiweMessage {
+            domain,
+            address,
+            statement,
+            uri: uri.ok_or("Missing URI")?,
+            version: version.ok_or("Missing version")?,
+            chain_id: chain_id.ok_or("Missing chain ID")?,
+            nonce,
+            issued_at: issued_at.ok_or("Missing issued-at")?,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+impl SiweMessage {
+    /// Checks the message against the node's expectations and the sign-in request.
+    /// `max_age` bounds how old `issued_at` may be, so a signed message cannot
+    /// outlive the nonce it was issued for.
+    pub fn validate(
+        &self,
+        config: &SiweConfig,
+        address: &str,
+        nonce: &str,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        if self.domain != config.domain {
+            return Err("SIWE domain does not match this service".to_string());
+        }
+        if !self.address.eq_ignore_ascii_case(address) {
+            return Err("SIWE address does not match request address".to_string());
+        }
+        if let Some(expected_uri) = &config.uri {
+            if !self.uri.starts_with(expected_uri.as_str()) {
+                return Err("SIWE URI does not match this service".to_string());
+            }
+        }
+        if self.version != SUPPORTED_VERSION {
+            return Err("Unsupported SIWE version".to_string());
+        }
+        if self.chain_id != config.chain_id {
+            return Err("SIWE chain ID does not match".to_string());
+        }
+        if self.nonce != nonce {
+            return Err("SIWE nonce does not match".to_string());
+        }
+        if self.issued_at > now + Duration::seconds(MAX_ISSUED_AT_SKEW_SECONDS) {
+            return Err("SIWE message issued in the future".to_string());
+        }
+        if self.issued_at < now - max_age {
+            return Err("SIWE message is too old".to_string());
+        }
+        if self.expiration_time.is_some_and(|expires| expires <= now) {
+            return Err("SIWE message has expired".to_string());
+        }
+        if self.not_before.is_some_and(|not_before| not_before > now) {
+            return Err("SIWE message is not yet valid".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::signature::recover_address_from_signature;
+
+    // Well-known test account (web3.js / ethers documentation key
+    // 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318).
+    const TEST_ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+    const SIWE_MESSAGE: &str = "localhost:5173 wants you to sign in with your Ethereum account:
+0x2c7536E3605D9C16a7a3D7b1898e529396a65c23
+
+Sign in to Hikmalayer.
+
+URI: http://localhost:5173
+Version: 1
+Chain ID: 1
+Nonce: 3f1c2a9e-5b7d-4e8f-9a0b-1c2d3e4f5a6b
+Issued At: 2026-01-01T00:00:00Z
+Expiration Time: 2026-01-01T00:10:00Z";
+
+    // personal_sign output for SIWE_MESSAGE from the test account.
+    const SIWE_SIGNATURE: &str = "0x589a3738293c4677b5f332f79ad142ce2cc32524935d7dced98a864a7408fa620e3a434280bf433d61f8029d523a2ef762fe292b9e8c775a270104bd162fe3881b";
+
+    fn now() -> DateTime<Utc> {
+        parse_timestamp("now", "2026-01-01T00:01:00Z").unwrap()
+    }
+
+    #[test]
+    fn recovers_known_personal_sign_vector() {
+        let recovered = recover_address_from_signature(
+            "Some data",
+            "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c",
+        )
+        .unwrap();
+        assert!(recovered.eq_ignore_ascii_case(TEST_ADDRESS));
+    }
+
+    #[test]
+    fn parses_and_validates_signed_siwe_message() {
+        let message: SiweMessage = SIWE_MESSAGE.parse().unwrap();
+        assert_eq!(message.domain, "localhost:5173");
+        assert_eq!(message.statement.as_deref(), Some("Sign in to Hikmalayer."));
+        assert_eq!(message.chain_id, 1);
+
+        let recovered = recover_address_from_signature(SIWE_MESSAGE, SIWE_SIGNATURE).unwrap();
+        assert!(recovered.eq_ignore_ascii_case(&message.address));
+
+        let config = SiweConfig::default();
+        let nonce = "3f1c2a9e-5b7d-4e8f-9a0b-1c2d3e4f5a6b";
+        let max_age = Duration::minutes(5);
+        assert!(message
+            .validate(&config, TEST_ADDRESS, nonce, max_age, now())
+            .is_ok());
+        assert!(message
+            .validate(&config, TEST_ADDRESS, "another-nonce", max_age, now())
+            .is_err());
+
+        let expired = parse_timestamp("now", "2026-01-01T00:11:00Z").unwrap();
+        assert!(message
+            .validate(&config, TEST_ADDRESS, nonce, Duration::hours(1), expired)
+            .is_err());
+
+        let other_domain = SiweConfig {
+            domain: "evil.example".to_string(),
+            ..SiweConfig::default()
+        };
+        assert!(message
+            .validate(&other_domain, TEST_ADDRESS, nonce, max_age, now())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_plain_messages() {
+        assert!("Please sign this message. Nonce: abc"
+            .parse::<SiweMessage>()
+            .is_err());
+    }
+}
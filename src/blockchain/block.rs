@@ -1,14 +1,17 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use std::sync::atomic::AtomicBool;
+
+use super::chain::ChainError;
+use super::transaction::Transaction;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub index: u64,
     pub timestamp: DateTime<Utc>,
-    pub transactions: Vec<String>,
+    pub transactions: Vec<BlockTransaction>,
     pub previous_hash: String,
-    pub difficulty: usize,
+    pub difficulty: u32,
     pub nonce: u64,
     pub hash: String,
     pub validator: Option<String>,
@@ -18,41 +21,116 @@ pub struct Block {
     pub staker_snapshot: Option<Vec<crate::consensus::pos::Staker>>,
 }
 
+/// An entry in a block's transaction list: a mined [`Transaction`], or a
+/// plain note such as the genesis marker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockTransaction {
+    Transaction(Transaction),
+    Note(String),
+}
+
+impl BlockTransaction {
+    pub fn as_transaction(&self) -> Option<&Transaction> {
+        match self {
+            BlockTransaction::Transaction(tx) => Some(tx),
+            BlockTransaction::Note(_) => None,
+        }
+    }
+
+    /// The string this entry contributes to the block hash. Transactions
+    /// hash as their `Debug` form, which is how blocks have always stored
+    /// them.
+    fn hash_entry(&self) -> String {
+        match self {
+            BlockTransaction::Transaction(tx) => format!("{:?}", tx),
+            BlockTransaction::Note(note) => note.clone(),
+        }
+    }
+}
+
+impl From<Transaction> for BlockTransaction {
+    fn from(tx: Transaction) -> Self {
+        BlockTransaction::Transaction(tx)
+    }
+}
+
+impl From<&str> for BlockTransaction {
+    fn from(note: &str) -> Self {
+        BlockTransaction::Note(note.to_string())
+    }
+}
+
+/// Block metadata without the transaction list or staker snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub index: u64,
+    pub timestamp: DateTime<Utc>,
+    pub previous_hash: String,
+    pub difficulty: u32,
+    pub nonce: u64,
+    pub hash: String,
+    pub validator: Option<String>,
+    pub validator_public_key: Option<String>,
+    pub validator_signature: Option<String>,
+    pub staker_set_hash: Option<String>,
+    pub transaction_count: usize,
+}
+
 use crate::consensus::pow;
 
 impl Block {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         index: u64,
-        transactions: Vec<String>,
+        transactions: Vec<BlockTransaction>,
         previous_hash: String,
-        difficulty: usize,
+        difficulty: u32,
         validator: Option<String>,
         validator_public_key: Option<String>,
         validator_signature: Option<String>,
         staker_set_hash: Option<String>,
         staker_snapshot: Option<Vec<crate::consensus::pos::Staker>>,
     ) -> Self {
-        let timestamp = Utc::now();
-        let data = Block::hash_payload(
-            &index,
-            &transactions,
-            &timestamp,
-            &validator,
-            &validator_public_key,
-            &staker_set_hash,
-            &previous_hash,
+        let mut block = Block::template(
+            index,
+            transactions,
+            previous_hash,
+            difficulty,
+            validator,
+            validator_public_key,
+            validator_signature,
+            staker_set_hash,
+            staker_snapshot,
         );
+        let (nonce, hash) = pow::mine_block(block.hash_data().as_bytes(), difficulty);
+        block.nonce = nonce;
+        block.hash = hex::encode(hash);
+        block
+    }
 
-        let (nonce, hash) = pow::mine_block(&data, difficulty);
-
+    /// A block with every field but the proof of work filled in; see
+    /// [`Block::mine`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn template(
+        index: u64,
+        transactions: Vec<BlockTransaction>,
+        previous_hash: String,
+        difficulty: u32,
+        validator: Option<String>,
+        validator_public_key: Option<String>,
+        validator_signature: Option<String>,
+        staker_set_hash: Option<String>,
+        staker_snapshot: Option<Vec<crate::consensus::pos::Staker>>,
+    ) -> Self {
         Block {
             index,
-            timestamp,
+            timestamp: Utc::now(),
             transactions,
             previous_hash,
             difficulty,
-            nonce,
-            hash,
+            nonce: 0,
+            hash: String::new(),
             validator,
             validator_public_key,
             validator_signature,
@@ -61,15 +139,50 @@ impl Block {
         }
     }
 
+    /// Searches for the nonce and hash on every core. Returns `Ok(false)`,
+    /// leaving the block unmined, if `cancel` is set first.
+    pub fn mine(&mut self, cancel: &AtomicBool) -> Result<bool, ChainError> {
+        if pow::target_from_bits(self.difficulty).is_none() {
+            return Err(ChainError::InvalidDifficulty(self.difficulty));
+        }
+        let prefix = self.hash_data();
+        let threads = pow::default_threads();
+        match pow::mine_block_until(prefix.as_bytes(), self.difficulty, threads, cancel) {
+            Some((nonce, hash)) => {
+                self.nonce = nonce;
+                self.hash = hex::encode(hash);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// The header prefix the proof of work is computed over.
+    fn hash_data(&self) -> String {
+        Block::hash_payload(
+            &self.index,
+            &self.transactions,
+            &self.timestamp,
+            &self.validator,
+            &self.validator_public_key,
+            &self.staker_set_hash,
+            &self.previous_hash,
+        )
+    }
+
     fn hash_payload(
         index: &u64,
-        transactions: &Vec<String>,
+        transactions: &[BlockTransaction],
         timestamp: &DateTime<Utc>,
         validator: &Option<String>,
         validator_public_key: &Option<String>,
         staker_set_hash: &Option<String>,
         previous_hash: &str,
     ) -> String {
+        let transactions: Vec<String> = transactions
+            .iter()
+            .map(BlockTransaction::hash_entry)
+            .collect();
         format!(
             "{:?}{:?}{:?}{:?}{:?}{:?}{}",
             index,
@@ -82,38 +195,56 @@ impl Block {
         )
     }
 
-    pub fn calculate_hash(&self) -> String {
-        let data = Block::hash_payload(
-            &self.index,
-            &self.transactions,
-            &self.timestamp,
-            &self.validator,
-            &self.validator_public_key,
-            &self.staker_set_hash,
-            &self.previous_hash,
-        );
-        let candidate = format!("{}{}", data, self.nonce);
-        let mut hasher = Sha256::new();
-        hasher.update(candidate.as_bytes());
-        format!("{:x}", hasher.finalize())
+    pub fn has_valid_pow(&self) -> bool {
+        let hash = self.pow_hash();
+        self.hash == hex::encode(hash)
+            && pow::target_from_bits(self.difficulty)
+                .is_some_and(|target| pow::meets_target(&hash, &target))
     }
 
-    pub fn has_valid_pow(&self) -> bool {
-        self.hash == self.calculate_hash() && self.hash.starts_with(&"0".repeat(self.difficulty))
+    fn pow_hash(&self) -> pow::Hash {
+        pow::hash_with_nonce(self.hash_data().as_bytes(), self.nonce)
+    }
+
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            timestamp: self.timestamp,
+            previous_hash: self.previous_hash.clone(),
+            difficulty: self.difficulty,
+            nonce: self.nonce,
+            hash: self.hash.clone(),
+            validator: self.validator.clone(),
+            validator_public_key: self.validator_public_key.clone(),
+            validator_signature: self.validator_signature.clone(),
+            staker_set_hash: self.staker_set_hash.clone(),
+            transaction_count: self.transactions.len(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blockchain::transaction::TransactionType;
+    use crate::consensus::difficulty::GENESIS_BITS;
+
+    fn transfer() -> Transaction {
+        Transaction::new(
+            Some("Alice".to_string()),
+            "Bob".to_string(),
+            10,
+            TransactionType::Transfer,
+        )
+    }
 
     #[test]
     fn test_block_creation() {
         let block = Block::new(
             1,
-            vec!["Tx".to_string()],
+            vec![transfer().into()],
             "abc".to_string(),
-            2,
+            GENESIS_BITS,
             Some("validator-1".to_string()),
             Some("validator-pubkey".to_string()),
             None,
@@ -122,4 +253,55 @@ mod tests {
         );
         assert_eq!(block.index, 1);
     }
+
+    #[test]
+    fn test_mining_rejects_invalid_bits() {
+        let mut block = Block::template(
+            1,
+            Vec::new(),
+            "abc".to_string(),
+            2,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            block.mine(&AtomicBool::new(false)),
+            Err(ChainError::InvalidDifficulty(2))
+        );
+        assert!(!block.has_valid_pow());
+    }
+
+    #[test]
+    fn test_block_header_omits_body() {
+        let block = Block::new(
+            1,
+            vec![transfer().into(), "Note".into()],
+            "abc".to_string(),
+            GENESIS_BITS,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let header = block.header();
+        assert_eq!(header.hash, block.hash);
+        assert_eq!(header.transaction_count, 2);
+
+        let json = serde_json::to_value(&header).unwrap();
+        assert!(json.get("transactions").is_none());
+        assert!(json.get("staker_snapshot").is_none());
+
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["transactions"][0]["to"], "Bob");
+        assert_eq!(json["transactions"][0]["transaction_type"], "Transfer");
+        assert_eq!(json["transactions"][1], "Note");
+
+        let parsed: Block = serde_json::from_value(json).unwrap();
+        assert!(parsed.transactions[0].as_transaction().is_some());
+        assert!(parsed.has_valid_pow());
+    }
 }
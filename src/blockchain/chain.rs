@@ -1,34 +1,102 @@
-use super::block::Block;
-use crate::consensus::pos::{self, Staker};
-use chrono::Utc;
+use super::block::{Block, BlockTransaction};
+use crate::consensus::{
+    difficulty,
+    pos::{self, Staker},
+};
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How far past this node's clock a block's timestamp may be.
+const MAX_FUTURE_BLOCK_SECONDS: i64 = 120;
+
+/// Why a block was rejected or could not be examined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    IndexMismatch {
+        expected: u64,
+        actual: u64,
+    },
+    PreviousHashMismatch,
+    DifficultyMismatch {
+        expected: u32,
+        actual: u32,
+    },
+    /// The compact bits do not decode to a target.
+    InvalidDifficulty(u32),
+    /// Earlier than its parent or too far in the future.
+    InvalidTimestamp,
+    /// Names the validator data the block lacks.
+    MissingValidatorData(&'static str),
+    StakerSetHashMismatch,
+    WrongValidator,
+    InvalidSignature,
+    InvalidPow,
+    GenesisNotSlashable,
+    BlockNotFound(u64),
+    NotSlashable,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::IndexMismatch { expected, actual } => write!(
+                f,
+                "Block index {} does not match chain tip, expected {}",
+                actual, expected
+            ),
+            ChainError::PreviousHashMismatch => {
+                write!(f, "Block previous hash does not match chain tip")
+            }
+            ChainError::DifficultyMismatch { expected, actual } => write!(
+                f,
+                "Block difficulty {:#010x} does not match the required {:#010x}",
+                actual, expected
+            ),
+            ChainError::InvalidDifficulty(bits) => {
+                write!(f, "Block difficulty {:#010x} is not a valid target", bits)
+            }
+            ChainError::InvalidTimestamp => {
+                write!(f, "Block timestamp is before its parent or in the future")
+            }
+            ChainError::MissingValidatorData(data) => write!(f, "Block missing {}", data),
+            ChainError::StakerSetHashMismatch => write!(f, "Block staker set hash mismatch"),
+            ChainError::WrongValidator => {
+                write!(f, "Block validator does not match PoS selection")
+            }
+            ChainError::InvalidSignature => write!(f, "Block signature verification failed"),
+            ChainError::InvalidPow => write!(f, "Block PoW validation failed"),
+            ChainError::GenesisNotSlashable => write!(f, "Cannot slash genesis block"),
+            ChainError::BlockNotFound(index) => write!(f, "Block {} not found", index),
+            ChainError::NotSlashable => write!(f, "Block does not contain slashable behavior"),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Blockchain {
     pub blocks: Vec<Block>,
-    pub difficulty: usize,
     #[serde(default)]
     pub finalized_height: u64,
 }
 
 impl Blockchain {
-    pub fn new(difficulty: usize) -> Self {
-        let genesis_block = Block::new(
-            0,
-            vec!["Genesis Block".to_string()],
-            "0".to_string(),
-            difficulty,
-            None,
-            None,
-            None,
-            None,
-            None,
-        );
-        Blockchain {
-            blocks: vec![genesis_block],
-            difficulty,
+    pub fn new() -> Self {
+        let mut chain = Blockchain {
+            blocks: Vec::new(),
             finalized_height: 0,
-        }
+        };
+        // An empty chain mines its first block at GENESIS_BITS on top of "0"
+        chain.add_block(vec!["Genesis Block".into()], None, None, None, None, None);
+        chain
+    }
+
+    /// The compact target the next block must meet; see
+    /// [`difficulty::next_bits`].
+    pub fn next_difficulty(&self) -> u32 {
+        difficulty::next_bits(&self.blocks)
     }
 
     pub fn latest_hash(&self) -> String {
@@ -40,32 +108,27 @@ impl Blockchain {
 
     pub fn add_block(
         &mut self,
-        transactions: Vec<String>,
+        transactions: Vec<BlockTransaction>,
         validator: Option<String>,
         validator_public_key: Option<String>,
         validator_signature: Option<String>,
         staker_set_hash: Option<String>,
         staker_snapshot: Option<Vec<Staker>>,
     ) {
-        let index = self.blocks.len() as u64;
-        let previous_hash = self.latest_hash();
-        let block = Block::new(
-            index,
+        let mut block = self.create_block(
             transactions,
-            previous_hash,
-            self.difficulty,
             validator,
             validator_public_key,
-            validator_signature,
             staker_set_hash,
             staker_snapshot,
         );
+        block.validator_signature = validator_signature;
         self.blocks.push(block);
     }
 
     pub fn create_block(
         &self,
-        transactions: Vec<String>,
+        transactions: Vec<BlockTransaction>,
         validator: Option<String>,
         validator_public_key: Option<String>,
         staker_set_hash: Option<String>,
@@ -77,7 +140,31 @@ impl Blockchain {
             index,
             transactions,
             previous_hash,
-            self.difficulty,
+            self.next_difficulty(),
+            validator,
+            validator_public_key,
+            None,
+            staker_set_hash,
+            staker_snapshot,
+        )
+    }
+
+    /// The next block on this chain, ready to be mined off the lock.
+    pub fn block_template(
+        &self,
+        transactions: Vec<BlockTransaction>,
+        validator: Option<String>,
+        validator_public_key: Option<String>,
+        staker_set_hash: Option<String>,
+        staker_snapshot: Option<Vec<Staker>>,
+    ) -> Block {
+        let index = self.blocks.len() as u64;
+        let previous_hash = self.latest_hash();
+        Block::template(
+            index,
+            transactions,
+            previous_hash,
+            self.next_difficulty(),
             validator,
             validator_public_key,
             None,
@@ -86,6 +173,16 @@ impl Blockchain {
         )
     }
 
+    pub fn block_by_hash(&self, hash: &str) -> Option<&Block> {
+        self.blocks.iter().find(|block| block.hash == hash)
+    }
+
+    pub fn blocks_page(&self, from: usize, limit: usize) -> &[Block] {
+        let start = from.min(self.blocks.len());
+        let end = start.saturating_add(limit).min(self.blocks.len());
+        &self.blocks[start..end]
+    }
+
     pub fn add_mined_block(&mut self, block: Block) {
         self.blocks.push(block);
     }
@@ -107,14 +204,35 @@ impl Blockchain {
         }
     }
 
-    pub fn validate_block_candidate(&self, block: &Block) -> Result<(), String> {
+    pub fn validate_block_candidate(&self, block: &Block) -> Result<(), ChainError> {
         let expected_index = self.blocks.len() as u64;
         if block.index != expected_index {
-            return Err("Block index does not match chain tip".to_string());
+            return Err(ChainError::IndexMismatch {
+                expected: expected_index,
+                actual: block.index,
+            });
         }
 
         if block.previous_hash != self.latest_hash() {
-            return Err("Block previous hash does not match chain tip".to_string());
+            return Err(ChainError::PreviousHashMismatch);
+        }
+
+        let expected_difficulty = self.next_difficulty();
+        if block.difficulty != expected_difficulty {
+            return Err(ChainError::DifficultyMismatch {
+                expected: expected_difficulty,
+                actual: block.difficulty,
+            });
+        }
+
+        let too_late = Utc::now() + Duration::seconds(MAX_FUTURE_BLOCK_SECONDS);
+        if self
+            .blocks
+            .last()
+            .is_some_and(|parent| block.timestamp < parent.timestamp)
+            || block.timestamp > too_late
+        {
+            return Err(ChainError::InvalidTimestamp);
         }
 
         if block.validator.is_none()
@@ -123,41 +241,41 @@ impl Blockchain {
             || block.staker_snapshot.is_none()
             || block.staker_set_hash.is_none()
         {
-            return Err("Block missing required validator or staker data".to_string());
+            return Err(ChainError::MissingValidatorData("validator or staker data"));
         }
 
         let staker_snapshot = block.staker_snapshot.as_ref().unwrap();
         let staker_hash = pos::staker_set_hash(staker_snapshot);
         if Some(staker_hash) != block.staker_set_hash {
-            return Err("Block staker set hash mismatch".to_string());
+            return Err(ChainError::StakerSetHashMismatch);
         }
 
         let expected_validator =
             pos::select_staker_with_seed(&block.previous_hash, staker_snapshot);
         if expected_validator != block.validator {
-            return Err("Block validator does not match PoS selection".to_string());
+            return Err(ChainError::WrongValidator);
         }
 
         let public_key = block.validator_public_key.as_ref().unwrap();
         let signature = block.validator_signature.as_ref().unwrap();
         if !pos::verify_block_signature(&block.hash, public_key, signature) {
-            return Err("Block signature verification failed".to_string());
+            return Err(ChainError::InvalidSignature);
         }
 
         if !block.has_valid_pow() {
-            return Err("Block PoW validation failed".to_string());
+            return Err(ChainError::InvalidPow);
         }
 
         Ok(())
     }
 
-    pub fn evaluate_slash_evidence(&self, block_index: u64) -> Result<SlashEvidence, String> {
+    pub fn evaluate_slash_evidence(&self, block_index: u64) -> Result<SlashEvidence, ChainError> {
         let index = block_index as usize;
         if index == 0 {
-            return Err("Cannot slash genesis block".to_string());
+            return Err(ChainError::GenesisNotSlashable);
         }
         if index >= self.blocks.len() {
-            return Err("Block index out of range".to_string());
+            return Err(ChainError::BlockNotFound(block_index));
         }
 
         let block = &self.blocks[index];
@@ -177,20 +295,20 @@ impl Blockchain {
         let validator = block
             .validator
             .clone()
-            .ok_or_else(|| "Block missing validator".to_string())?;
+            .ok_or(ChainError::MissingValidatorData("validator"))?;
         let public_key = block
             .validator_public_key
             .as_ref()
-            .ok_or_else(|| "Block missing validator public key".to_string())?;
+            .ok_or(ChainError::MissingValidatorData("validator public key"))?;
         let signature = block
             .validator_signature
             .as_ref()
-            .ok_or_else(|| "Block missing validator signature".to_string())?;
+            .ok_or(ChainError::MissingValidatorData("validator signature"))?;
 
         let staker_snapshot = block
             .staker_snapshot
             .as_ref()
-            .ok_or_else(|| "Block missing staker snapshot".to_string())?;
+            .ok_or(ChainError::MissingValidatorData("staker snapshot"))?;
         let staker_hash = pos::staker_set_hash(staker_snapshot);
         if Some(staker_hash) != block.staker_set_hash {
             return Ok(SlashEvidence {
@@ -218,6 +336,14 @@ impl Blockchain {
             });
         }
 
+        if block.difficulty != difficulty::next_bits(&self.blocks[..index]) {
+            return Ok(SlashEvidence {
+                validator,
+                reason: "Difficulty does not match the retarget".to_string(),
+                timestamp: Utc::now().to_rfc3339(),
+            });
+        }
+
         if !block.has_valid_pow() {
             return Ok(SlashEvidence {
                 validator,
@@ -226,7 +352,7 @@ impl Blockchain {
             });
         }
 
-        Err("Block does not contain slashable behavior".to_string())
+        Err(ChainError::NotSlashable)
     }
 
     pub fn is_valid(&self) -> bool {
@@ -240,7 +366,10 @@ impl Blockchain {
             let current = &self.blocks[i];
             let previous = &self.blocks[i - 1];
 
-            if current.previous_hash != previous.hash {
+            if current.previous_hash != previous.hash
+                || current.timestamp < previous.timestamp
+                || current.difficulty != difficulty::next_bits(&self.blocks[..i])
+            {
                 return false;
             }
 
@@ -280,7 +409,7 @@ impl Blockchain {
 
     pub fn validate_and_slash(
         &self,
-        stakers: &mut Vec<Staker>,
+        stakers: &mut [Staker],
     ) -> (bool, Vec<(String, u64)>, Option<String>) {
         let mut slashed = Vec::new();
 
@@ -388,6 +517,30 @@ impl Blockchain {
                 );
             }
 
+            if current.timestamp < previous.timestamp {
+                return (
+                    false,
+                    slashed,
+                    Some(format!("Block {} is timestamped before its parent", i)),
+                );
+            }
+
+            let expected_difficulty = difficulty::next_bits(&self.blocks[..i]);
+            if current.difficulty != expected_difficulty {
+                let amount = pos::slash_staker(stakers, validator);
+                if amount > 0 {
+                    slashed.push((validator.clone(), amount));
+                }
+                return (
+                    false,
+                    slashed,
+                    Some(format!(
+                        "Block {} has difficulty {:#010x}, expected {:#010x}",
+                        i, current.difficulty, expected_difficulty
+                    )),
+                );
+            }
+
             if !current.has_valid_pow() {
                 let amount = pos::slash_staker(stakers, validator);
                 if amount > 0 {
@@ -407,7 +560,7 @@ impl Blockchain {
 
 impl Default for Blockchain {
     fn default() -> Self {
-        Blockchain::new(2)
+        Blockchain::new()
     }
 }
 
@@ -447,7 +600,7 @@ mod tests {
         }];
         let staker_hash = pos::staker_set_hash(&stakers);
         let mut block = chain.create_block(
-            vec!["Tx1".to_string()],
+            vec!["Tx1".into()],
             Some("validator-1".to_string()),
             Some(public_key),
             Some(staker_hash),
@@ -471,7 +624,7 @@ mod tests {
         }];
         let staker_hash = pos::staker_set_hash(&stakers);
         let mut block = chain.create_block(
-            vec!["Tx1".to_string()],
+            vec!["Tx1".into()],
             Some("validator-1".to_string()),
             Some(public_key),
             Some(staker_hash),
@@ -481,5 +634,21 @@ mod tests {
         block.validator_signature = Some(signature);
         chain.add_mined_block(block);
         assert!(chain.is_valid());
+
+        // The difficulty field must match the retarget even with a valid PoW
+        chain.blocks[1].difficulty = difficulty::POW_LIMIT_BITS;
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_block_lookup_and_paging() {
+        let chain = Blockchain::default();
+        let genesis_hash = chain.blocks[0].hash.clone();
+        assert_eq!(chain.block_by_hash(&genesis_hash).unwrap().index, 0);
+        assert!(chain.block_by_hash("missing").is_none());
+
+        assert_eq!(chain.blocks_page(0, 10).len(), 1);
+        assert!(chain.blocks_page(5, 10).is_empty());
+        assert!(chain.blocks_page(0, 0).is_empty());
     }
 }
@@ -1,3 +1,4 @@
 pub mod block;
 pub mod chain;
+pub mod receipt;
 pub mod transaction;
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::transaction::{Transaction, TransactionType};
+use crate::events::EventLog;
+
+/// What a transaction did: whether it succeeded, the gas it used and the logs
+/// it emitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Receipt {
+    pub transaction_id: String,
+    pub transaction_type: TransactionType,
+    pub success: bool,
+    /// Zero for transactions that run no contract code.
+    pub gas_used: u64,
+    pub logs: Vec<EventLog>,
+    /// Set once the transaction is mined.
+    pub block_index: Option<u64>,
+    pub block_hash: Option<String>,
+}
+
+impl Receipt {
+    pub fn new(transaction: &Transaction, logs: Vec<EventLog>) -> Self {
+        Receipt {
+            transaction_id: transaction.id.clone(),
+            transaction_type: transaction.transaction_type.clone(),
+            success: true,
+            gas_used: 0,
+            logs,
+            block_index: None,
+            block_hash: None,
+        }
+    }
+
+    pub fn with_execution(mut self, success: bool, gas_used: u64) -> Self {
+        self.success = success;
+        self.gas_used = gas_used;
+        self
+    }
+}
+
+/// Filters for [`ReceiptStore::query_logs`]. Every given filter must match.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    pub topic: Option<String>,
+    pub contract: Option<String>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    /// Attribute values, compared case-insensitively.
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl LogQuery {
+    fn matches(&self, log: &EventLog) -> bool {
+        self.topic
+            .as_ref()
+            .is_none_or(|topic| log.topic.eq_ignore_ascii_case(topic))
+            && self
+                .contract
+                .as_ref()
+                .is_none_or(|contract| log.contract.eq_ignore_ascii_case(contract))
+            && self
+                .attributes
+                .iter()
+                .all(|(key, value)| log.has_attribute(key, value))
+    }
+}
+
+/// A mined log with the transaction and block that emitted it.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    #[serde(flatten)]
+    pub log: EventLog,
+    pub transaction_id: String,
+    pub block_index: u64,
+    pub block_hash: String,
+}
+
+/// Receipts of pending transactions and, once mined, of each block.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReceiptStore {
+    pending: Vec<Receipt>,
+    blocks: BTreeMap<u64, Vec<Receipt>>,
+}
+
+impl ReceiptStore {
+    pub fn record(&mut self, receipt: Receipt) {
+        self.pending.push(receipt);
+    }
+
+    /// Moves the receipts of the transactions mined in a block under that block.
+    pub fn seal(&mut self, block_index: u64, block_hash: &str, transaction_ids: &HashSet<String>) {
+        let (mut mined, pending): (Vec<Receipt>, Vec<Receipt>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|receipt| transaction_ids.contains(&receipt.transaction_id));
+        self.pending = pending;
+        for receipt in &mut mined {
+            receipt.block_index = Some(block_index);
+            receipt.block_hash = Some(block_hash.to_string());
+        }
+        if !mined.is_empty() {
+            self.blocks.insert(block_index, mined);
+        }
+    }
+
+    pub fn get(&self, transaction_id: &str) -> Option<&Receipt> {
+        self.pending
+            .iter()
+            .chain(self.blocks.values().flatten())
+            .find(|receipt| receipt.transaction_id == transaction_id)
+    }
+
+    pub fn block(&self, block_index: u64) -> &[Receipt] {
+        self.blocks
+            .get(&block_index)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Mined logs matching `query`, oldest first, at most `limit` of them.
+    pub fn query_logs(&self, query: &LogQuery, limit: usize) -> Vec<LogEntry> {
+        let (from, to) = (
+            query.from_block.unwrap_or(0),
+            query.to_block.unwrap_or(u64::MAX),
+        );
+        if from > to {
+            return Vec::new();
+        }
+        self.blocks
+            .range(from..=to)
+            .flat_map(|(_, receipts)| receipts)
+            .flat_map(|receipt| receipt.logs.iter().map(move |log| (receipt, log)))
+            .filter(|(_, log)| query.matches(log))
+            .take(limit)
+            .map(|(receipt, log)| LogEntry {
+                log: log.clone(),
+                transaction_id: receipt.transaction_id.clone(),
+                block_index: receipt.block_index.unwrap_or_default(),
+                block_hash: receipt.block_hash.clone().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{CERTIFICATE_CONTRACT, TOKEN_CONTRACT};
+
+    #[test]
+    fn seals_receipts_into_blocks_and_queries_logs() {
+        let issue = Transaction::new(
+            Some("0xuni".to_string()),
+            "alice".to_string(),
+            0,
+            TransactionType::Certificate,
+        );
+        let transfer = Transaction::new(
+            Some("alice".to_string()),
+            "bob".to_string(),
+            5,
+            TransactionType::Transfer,
+        );
+        let mut receipts = ReceiptStore::default();
+        receipts.record(Receipt::new(
+            &issue,
+            vec![EventLog::new(CERTIFICATE_CONTRACT, "CertificateIssued")
+                .with("id", "CERT001")
+                .with("holder", "Alice")],
+        ));
+        receipts.record(Receipt::new(
+            &transfer,
+            vec![EventLog::transfer("HKM", "alice", "bob", 5)],
+        ));
+
+        receipts.seal(1, "00ab", &HashSet::from([issue.id.clone()]));
+        assert_eq!(receipts.block(1).len(), 1);
+        assert_eq!(receipts.get(&transfer.id).unwrap().block_index, None);
+        receipts.seal(2, "00cd", &HashSet::from([transfer.id.clone()]));
+
+        let by_holder = LogQuery {
+            topic: Some("CertificateIssued".to_string()),
+            attributes: BTreeMap::from([("holder".to_string(), "alice".to_string())]),
+            ..LogQuery::default()
+        };
+        let logs = receipts.query_logs(&by_holder, 10);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].transaction_id, issue.id);
+
+        let token_from_block_2 = LogQuery {
+            contract: Some(TOKEN_CONTRACT.to_string()),
+            from_block: Some(2),
+            ..LogQuery::default()
+        };
+        assert_eq!(
+            receipts.query_logs(&token_from_block_2, 10)[0].block_index,
+            2
+        );
+        let before_block_2 = LogQuery {
+            to_block: Some(1),
+            ..token_from_block_2
+        };
+        assert!(receipts.query_logs(&before_block_2, 10).is_empty());
+    }
+}
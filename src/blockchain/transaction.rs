@@ -4,9 +4,21 @@ use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionType {
-    Transfer,    // Transfer tokens
-    Reward,      // PoS or PoW reward
-    Certificate, // Issue certificate
+    Transfer,          // Transfer tokens
+    TokenCreate,       // Create a token in the registry
+    TokenMint,         // Mint a registry token
+    TokenBurn,         // Burn tokens
+    TokenApprove,      // Set a spender's allowance
+    VestingGrant,      // Grant tokens under a vesting schedule
+    NftMint,           // Mint an NFT
+    NftTransfer,       // Transfer an NFT
+    NftBurn,           // Burn an NFT
+    Reward,            // PoS, PoW or certificate holder reward
+    Certificate,       // Issue certificate
+    CertificateStatus, // Suspend, reinstate or revoke a certificate
+    CertificateBatch,  // Anchor a batch Merkle root
+    ContractDeploy,    // Deploy a WASM contract
+    ContractCall,      // Call a WASM contract function
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +29,12 @@ pub struct Transaction {
     pub amount: u64,
     pub transaction_type: TransactionType,
     pub timestamp: DateTime<Utc>,
+    /// Id of the object the transaction acts on, e.g. a certificate id.
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// Gas fee paid in native tokens, on top of `amount`.
+    #[serde(default)]
+    pub fee: u64,
 }
 
 impl Transaction {
@@ -33,8 +51,20 @@ impl Transaction {
             amount,
             transaction_type,
             timestamp: Utc::now(),
+            reference: None,
+            fee: 0,
         }
     }
+
+    pub fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    pub fn with_fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
 }
 
 #[cfg(test)]
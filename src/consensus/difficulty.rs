@@ -0,0 +1,135 @@
+use super::pow::{self, Hash};
+use crate::blockchain::block::Block;
+
+/// Target of the genesis block and of every block until the first retarget:
+/// about one hash in 256.
+pub const GENESIS_BITS: u32 = 0x2000_ffff;
+/// Easiest target a retarget may reach.
+pub const POW_LIMIT_BITS: u32 = 0x2100_ffff;
+pub const TARGET_BLOCK_SECONDS: i64 = 30;
+/// Blocks between retargets.
+pub const RETARGET_INTERVAL: u64 = 10;
+/// One retarget changes the target by at most this factor either way.
+const MAX_ADJUSTMENT: i64 = 4;
+
+/// The `difficulty` (compact target) required of the block after `blocks`.
+///
+/// It stays the same as the previous block's except every
+/// `RETARGET_INTERVAL` blocks, when it is scaled by how long the last
+/// interval took against `TARGET_BLOCK_SECONDS` per block. A previous block
+/// whose bits are not a valid target restarts from `GENESIS_BITS`.
+pub fn next_bits(blocks: &[Block]) -> u32 {
+    let Some(last) = blocks.last() else {
+        return GENESIS_BITS;
+    };
+    let Some(target) = pow::target_from_bits(last.difficulty) else {
+        return GENESIS_BITS;
+    };
+    let height = blocks.len() as u64;
+    if !height.is_multiple_of(RETARGET_INTERVAL) {
+        return last.difficulty;
+    }
+
+    let first = &blocks[blocks.len() - RETARGET_INTERVAL as usize];
+    let expected = TARGET_BLOCK_SECONDS * (RETARGET_INTERVAL as i64 - 1);
+    let actual = (last.timestamp - first.timestamp)
+        .num_seconds()
+        .clamp(expected / MAX_ADJUSTMENT, expected * MAX_ADJUSTMENT);
+
+    let limit = pow::target_from_bits(POW_LIMIT_BITS).expect("POW_LIMIT_BITS is a valid target");
+    let retargeted = scale_target(&target, actual as u64, expected as u64);
+    pow::bits_from_target(&retargeted.min(limit))
+}
+
+/// Average number of hashes needed to meet `bits`, or `None` if it is not a
+/// valid target.
+pub fn expected_hashes(bits: u32) -> Option<f64> {
+    let target = pow::target_from_bits(bits)?;
+    let target = target
+        .iter()
+        .fold(0.0, |acc: f64, &byte| acc * 256.0 + byte as f64);
+    Some(2f64.powi(256) / (target + 1.0))
+}
+
+/// `target * numerator / denominator`, saturating at the largest hash.
+fn scale_target(target: &Hash, numerator: u64, denominator: u64) -> Hash {
+    // Little-endian 64-bit limbs, with a spare limb for overflow
+    let mut limbs = [0u64; 5];
+    for (i, limb) in limbs[..4].iter_mut().enumerate() {
+        let end = 32 - i * 8;
+        *limb = u64::from_be_bytes(target[end - 8..end].try_into().unwrap());
+    }
+
+    let mut carry = 0u128;
+    for limb in limbs.iter_mut() {
+        let product = *limb as u128 * numerator as u128 + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+    let mut remainder = 0u128;
+    for limb in limbs.iter_mut().rev() {
+        let dividend = (remainder << 64) | *limb as u128;
+        *limb = (dividend / denominator as u128) as u64;
+        remainder = dividend % denominator as u128;
+    }
+    if limbs[4] != 0 {
+        return [0xff; 32];
+    }
+
+    let mut scaled = [0u8; 32];
+    for (i, limb) in limbs[..4].iter().enumerate() {
+        let end = 32 - i * 8;
+        scaled[end - 8..end].copy_from_slice(&limb.to_be_bytes());
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn chain_with_spacing(seconds: i64) -> Vec<Block> {
+        let start = chrono::Utc::now();
+        (0..RETARGET_INTERVAL)
+            .map(|index| {
+                let mut block = Block::template(
+                    index,
+                    Vec::new(),
+                    String::new(),
+                    GENESIS_BITS,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                block.timestamp = start + Duration::seconds(seconds * index as i64);
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn retargets_from_block_timestamps() {
+        assert_eq!(next_bits(&[]), GENESIS_BITS);
+        let on_time = chain_with_spacing(TARGET_BLOCK_SECONDS);
+        assert_eq!(next_bits(&on_time[..5]), GENESIS_BITS);
+        assert_eq!(next_bits(&on_time), GENESIS_BITS);
+
+        // Blocks twice as fast halve the target; far too slow ones are capped
+        assert_eq!(
+            next_bits(&chain_with_spacing(TARGET_BLOCK_SECONDS / 2)),
+            0x1f7f_ff80
+        );
+        let slow = next_bits(&chain_with_spacing(TARGET_BLOCK_SECONDS * 100));
+        assert_eq!(slow, 0x2003_fffc);
+
+        // Bits that do not decode, such as an old leading-zero count
+        let mut legacy = chain_with_spacing(TARGET_BLOCK_SECONDS);
+        legacy.iter_mut().for_each(|block| block.difficulty = 2);
+        assert_eq!(next_bits(&legacy[..5]), GENESIS_BITS);
+        assert_eq!(next_bits(&legacy), GENESIS_BITS);
+        assert_eq!(expected_hashes(GENESIS_BITS).unwrap().round(), 256.0);
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::blockchain::block::Block;
+
+/// Finished jobs kept for status queries, oldest dropped first.
+const MAX_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum MiningStatus {
+    Mining,
+    Mined { hash: String, nonce: u64 },
+    Cancelled { reason: String },
+    Failed { reason: String },
+}
+
+/// A proof-of-work search for one block, run off the async runtime.
+#[derive(Debug, Clone, Serialize)]
+pub struct MiningJob {
+    pub id: String,
+    pub block_index: u64,
+    pub previous_hash: String,
+    pub difficulty: u32,
+    pub validator: Option<String>,
+    pub transaction_count: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: MiningStatus,
+    #[serde(skip)]
+    cancel: Arc<AtomicBool>,
+    /// Why the job was asked to stop, kept until its search notices.
+    #[serde(skip)]
+    cancel_reason: Option<String>,
+}
+
+impl MiningJob {
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, MiningStatus::Mining)
+    }
+}
+
+/// This node's mining jobs. At most one is active at a time, since every job
+/// mines on top of the current tip.
+#[derive(Debug, Default)]
+pub struct MiningJobs {
+    jobs: VecDeque<MiningJob>,
+}
+
+impl MiningJobs {
+    /// Registers a job for `template` and returns it with the flag that stops
+    /// its search, or the active job if there already is one.
+    pub fn start(&mut self, template: &Block) -> Result<(String, Arc<AtomicBool>), &MiningJob> {
+        if let Some(index) = self.jobs.iter().position(MiningJob::is_active) {
+            return Err(&self.jobs[index]);
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        let job = MiningJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            block_index: template.index,
+            previous_hash: template.previous_hash.clone(),
+            difficulty: template.difficulty,
+            validator: template.validator.clone(),
+            transaction_count: template.transactions.len(),
+            started_at: Utc::now(),
+            finished_at: None,
+            status: MiningStatus::Mining,
+            cancel: cancel.clone(),
+            cancel_reason: None,
+        };
+        let id = job.id.clone();
+        self.jobs.push_back(job);
+        while self.jobs.len() > MAX_FINISHED_JOBS + 1 {
+            self.jobs.pop_front();
+        }
+        Ok((id, cancel))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&MiningJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Most recent first.
+    pub fn list(&self) -> impl Iterator<Item = &MiningJob> {
+        self.jobs.iter().rev()
+    }
+
+    pub fn finish(&mut self, id: &str, status: MiningStatus) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.status = status;
+            job.finished_at = Some(Utc::now());
+        }
+    }
+
+    /// Stops the active job if a block at `height` received from a peer makes
+    /// it stale, returning its id. The job is marked cancelled, with that
+    /// block as the reason, once its search notices.
+    pub fn cancel_stale(&mut self, height: u64) -> Option<String> {
+        let job = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.is_active() && job.block_index <= height)?;
+        job.cancel.store(true, Ordering::Relaxed);
+        job.cancel_reason = Some(format!("Block {} arrived from a peer", height));
+        Some(job.id.clone())
+    }
+
+    /// Marks the job cancelled for the reason [`MiningJobs::cancel_stale`]
+    /// recorded, or `fallback`, and returns the reason.
+    pub fn finish_cancelled(&mut self, id: &str, fallback: &str) -> String {
+        let reason = self
+            .get(id)
+            .and_then(|job| job.cancel_reason.clone())
+            .unwrap_or_else(|| fallback.to_string());
+        self.finish(
+            id,
+            MiningStatus::Cancelled {
+                reason: reason.clone(),
+            },
+        );
+        reason
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn competing_block_cancels_active_job() {
+        let mut jobs = MiningJobs::default();
+        let template = Block::template(
+            3,
+            Vec::new(),
+            "abc".to_string(),
+            0x2000_ffff,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let (id, cancel) = jobs.start(&template).unwrap();
+        assert_eq!(jobs.start(&template).unwrap_err().id, id);
+
+        assert_eq!(jobs.cancel_stale(2), None);
+        assert_eq!(jobs.cancel_stale(3), Some(id.clone()));
+        assert!(cancel.load(Ordering::Relaxed));
+
+        assert_eq!(
+            jobs.finish_cancelled(&id, "stale"),
+            "Block 3 arrived from a peer"
+        );
+        let job = jobs.get(&id).unwrap();
+        assert!(!job.is_active());
+        assert!(matches!(
+            &job.status,
+            MiningStatus::Cancelled { reason } if reason == "Block 3 arrived from a peer"
+        ));
+        assert!(jobs.start(&template).is_ok());
+    }
+}
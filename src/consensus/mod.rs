@@ -1,2 +1,4 @@
+pub mod difficulty;
+pub mod mining;
 pub mod pos;
 pub mod pow;
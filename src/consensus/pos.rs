@@ -52,6 +52,7 @@ pub fn select_staker_with_seed(seed: &str, stakers: &[Staker]) -> Option<String>
     None
 }
 
+#[allow(dead_code)]
 pub fn select_staker(stakers: &[Staker]) -> Option<String> {
     let total_stake: u64 = stakers.iter().map(|s| s.stake).sum();
 
@@ -105,11 +106,11 @@ pub fn verify_block_signature(block_hash: &str, public_key_hex: &str, signature_
     secp.verify_ecdsa(&message, &signature, &public_key).is_ok()
 }
 
-pub fn slash_staker(stakers: &mut Vec<Staker>, address: &str) -> u64 {
+pub fn slash_staker(stakers: &mut [Staker], address: &str) -> u64 {
     slash_staker_with_percent(stakers, address, SLASH_PERCENT)
 }
 
-pub fn slash_staker_with_percent(stakers: &mut Vec<Staker>, address: &str, percent: u64) -> u64 {
+pub fn slash_staker_with_percent(stakers: &mut [Staker], address: &str, percent: u64) -> u64 {
     for staker in stakers.iter_mut() {
         if staker.address == address {
             let slashed = staker.stake.saturating_mul(percent) / 100;
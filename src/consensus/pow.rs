@@ -1,19 +1,163 @@
 use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
 
-/// Tries different nonces to find a hash starting with `difficulty` zeroes.
-pub fn mine_block(data: &str, difficulty: usize) -> (u64, String) {
-    let mut nonce = 0;
+/// Hashes between checks of the cancel and found flags.
+const CHECK_INTERVAL: u64 = 1024;
 
-    loop {
-        let candidate = format!("{}{}", data, nonce);
-        let mut hasher = Sha256::new();
-        hasher.update(candidate.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
+pub type Hash = [u8; 32];
+
+/// Proof-of-work hash of a block: SHA-256 of its header prefix followed by the
+/// nonce as 8 little-endian bytes.
+pub fn hash_with_nonce(prefix: &[u8], nonce: u64) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(prefix);
+    hasher.update(nonce.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Expands a compact ("nBits") target: the high byte is the target's length
+/// in bytes and the low three bytes its most significant digits, as in
+/// Bitcoin. Returns `None` for zero, negative or overflowing targets.
+pub fn target_from_bits(bits: u32) -> Option<Hash> {
+    let size = (bits >> 24) as usize;
+    let mantissa = bits & 0x007f_ffff;
+    if mantissa == 0 || bits & 0x0080_0000 != 0 {
+        return None;
+    }
+
+    let mut target = [0u8; 32];
+    for (i, &byte) in mantissa.to_be_bytes()[1..].iter().enumerate() {
+        // Position of this byte counted from the least significant end, from 1
+        let position = size as i64 - i as i64;
+        if position <= 0 {
+            continue;
+        }
+        if position > 32 {
+            if byte != 0 {
+                return None;
+            }
+            continue;
+        }
+        target[32 - position as usize] = byte;
+    }
+    (target != [0u8; 32]).then_some(target)
+}
+
+/// Compacts a target into nBits, rounding it down to three significant bytes.
+pub fn bits_from_target(target: &Hash) -> u32 {
+    let Some(first) = target.iter().position(|&byte| byte != 0) else {
+        return 0;
+    };
+    let mut size = (32 - first) as u32;
+    let mut mantissa = target[first..]
+        .iter()
+        .chain([0u8; 2].iter())
+        .take(3)
+        .fold(0u32, |acc, &byte| (acc << 8) | byte as u32);
+    // The top mantissa bit is a sign bit, so move a set one into the size
+    if mantissa & 0x0080_0000 != 0 {
+        mantissa >>= 8;
+        size += 1;
+    }
+    (size << 24) | mantissa
+}
+
+/// Hashes compare as big-endian 256-bit numbers.
+pub fn meets_target(hash: &Hash, target: &Hash) -> bool {
+    hash <= target
+}
 
-        if hash.starts_with(&"0".repeat(difficulty)) {
-            return (nonce, hash);
+/// Searches for a nonce whose hash meets the compact target `bits`, using
+/// all available cores. Panics if `bits` is not a valid target.
+pub fn mine_block(prefix: &[u8], bits: u32) -> (u64, Hash) {
+    mine_block_until(prefix, bits, default_threads(), &AtomicBool::new(false))
+        .expect("valid bits without a cancel flag always finish")
+}
+
+/// Like [`mine_block`] on `threads` threads, but gives up and returns `None`
+/// once `cancel` is set or if `bits` is invalid. Thread `i` tries nonces `i`, `i + threads`, ..., so
+/// the nonce found is not necessarily the lowest.
+pub fn mine_block_until(
+    prefix: &[u8],
+    bits: u32,
+    threads: usize,
+    cancel: &AtomicBool,
+) -> Option<(u64, Hash)> {
+    let target = target_from_bits(bits)?;
+    let threads = threads.max(1) as u64;
+    let found = AtomicBool::new(false);
+
+    // The prefix is hashed once; each nonce only finishes a copy of that state
+    let mut midstate = Sha256::new();
+    midstate.update(prefix);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|start| {
+                let (midstate, target, found) = (&midstate, &target, &found);
+                scope.spawn(move || search(midstate, target, start, threads, cancel, found))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .filter_map(|worker| worker.join().expect("mining thread panicked"))
+            .min_by_key(|(nonce, _)| *nonce)
+    })
+}
+
+fn search(
+    midstate: &Sha256,
+    target: &Hash,
+    start: u64,
+    step: u64,
+    cancel: &AtomicBool,
+    found: &AtomicBool,
+) -> Option<(u64, Hash)> {
+    let mut nonce = start;
+    loop {
+        for _ in 0..CHECK_INTERVAL {
+            let mut hasher = midstate.clone();
+            hasher.update(nonce.to_le_bytes());
+            let hash: Hash = hasher.finalize().into();
+            if meets_target(&hash, target) {
+                found.store(true, Ordering::Relaxed);
+                return Some((nonce, hash));
+            }
+            nonce = nonce.wrapping_add(step);
+        }
+        if found.load(Ordering::Relaxed) || cancel.load(Ordering::Relaxed) {
+            return None;
         }
+    }
+}
+
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_bits_round_trip_and_mine() {
+        // About one hash in 65,536: four leading zero hex digits
+        let target = target_from_bits(0x1f00_ffff).unwrap();
+        assert_eq!(target[..5], [0x00, 0x00, 0xff, 0xff, 0x00]);
+        assert_eq!(bits_from_target(&target), 0x1f00_ffff);
+        assert_eq!(
+            target_from_bits(0x0312_3456).unwrap()[29..],
+            [0x12, 0x34, 0x56]
+        );
+        assert_eq!(target_from_bits(0x0180_0000), None);
+        assert_eq!(target_from_bits(0x2212_3456), None);
+
+        let (nonce, hash) =
+            mine_block_until(b"header", 0x2000_ffff, 4, &AtomicBool::new(false)).unwrap();
+        assert_eq!(hash, hash_with_nonce(b"header", nonce));
+        assert!(meets_target(&hash, &target_from_bits(0x2000_ffff).unwrap()));
 
-        nonce += 1;
+        assert!(mine_block_until(b"header", 0x0300_0001, 2, &AtomicBool::new(true)).is_none());
     }
 }
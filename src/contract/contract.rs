@@ -1,70 +1,1130 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
 
+use super::error::CertificateError;
+use super::issuers::IssuerRegistry;
+use super::merkle::{self, ProofStep};
+use super::runtime::{Runtime, WasmContract};
+use crate::auth::signature::recover_address_from_signature;
+use crate::blockchain::transaction::{Transaction, TransactionType};
+use crate::consensus::pos;
+use crate::events::{EventLog, CERTIFICATE_CONTRACT};
 use crate::token::fungible::Token;
 
+/// Location of the transaction that recorded a certificate on-chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockAnchor {
+    pub block_index: u64,
+    pub block_hash: String,
+    pub transaction_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateStatus {
+    #[default]
+    Active,
+    Suspended,
+    /// Terminal: a revoked certificate cannot be reinstated.
+    Revoked,
+    /// Derived from `expires_at` when reporting status; never stored.
+    Expired,
+}
+
+/// Why a certificate's status changed, loosely following X.509 CRL reason codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusReason {
+    IssuedInError,
+    Superseded,
+    Fraud,
+    KeyCompromise,
+    UnderInvestigation,
+    HolderRequest,
+    Resolved,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: CertificateStatus,
+    pub reason: StatusReason,
+    pub note: Option<String>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+    pub transaction_id: String,
+    /// Set once the status-change transaction is mined.
+    pub anchor: Option<BlockAnchor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// EIP-191 `personal_sign` over the signing payload; the signer address is recovered.
+    Ethereum,
+    /// Compact secp256k1 ECDSA over the SHA-256 of the signing payload, checked
+    /// against the issuer's registered public key.
+    Secp256k1,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssuerSignature {
+    pub scheme: SignatureScheme,
+    /// Hex-encoded signature, `0x` prefix optional.
+    pub signature: String,
+}
+
+/// Outcome of checking a certificate's issuer signature.
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureCheck {
+    /// The signature is well-formed and was made by the certificate's issuer.
+    pub signature_valid: bool,
+    /// The issuer is in the trusted issuer registry.
+    pub trusted_issuer: bool,
+    pub scheme: Option<SignatureScheme>,
+    /// Recovered address (Ethereum) or public key used (secp256k1).
+    pub signer: Option<String>,
+    pub message: String,
+}
+
+impl SignatureCheck {
+    pub fn is_valid(&self) -> bool {
+        self.signature_valid && self.trusted_issuer
+    }
+}
+
+/// Where a batch-issued certificate sits in its batch's Merkle tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchMembership {
+    /// Hex-encoded Merkle root anchored on-chain for the whole batch.
+    pub root: String,
+    pub proof: Vec<ProofStep>,
+}
+
+/// What a holder keeps to prove a batch-issued certificate, Blockcerts style:
+/// the signed payload, its hash, and the path to the anchored root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateReceipt {
+    pub certificate_id: String,
+    /// The certificate's signing payload.
+    pub payload: String,
+    /// Hex SHA-256 of `payload`; the Merkle leaf.
+    pub target_hash: String,
+    pub merkle_root: String,
+    pub proof: Vec<ProofStep>,
+    #[serde(default)]
+    pub anchor: Option<BlockAnchor>,
+}
+
+impl CertificateReceipt {
+    /// Checks the payload hashes to `target_hash` and the proof leads to `merkle_root`.
+    /// Whether the root is anchored on-chain is up to the caller.
+    pub fn proof_is_valid(&self) -> bool {
+        let leaf = merkle::hash_leaf(self.payload.as_bytes());
+        hex::encode(leaf).eq_ignore_ascii_case(&self.target_hash)
+            && merkle::verify_proof(&leaf, &self.proof, &self.merkle_root)
+    }
+}
+
+/// Certificates issued together under one anchored Merkle root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateBatch {
+    pub root: String,
+    pub issuer: String,
+    pub certificate_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Set once the batch transaction is mined.
+    pub anchor: Option<BlockAnchor>,
+}
+
+/// Account holding the budgets of every reward policy until they are paid.
+pub const REWARD_POOL_ACCOUNT: &str = "__reward_pool__";
+
+/// Tokens an issuer pays holders of one type of certificate, once per
+/// certificate that verifies. The budget is escrowed in
+/// [`REWARD_POOL_ACCOUNT`] when the policy is set, and rewards are paid from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewardPolicy {
+    pub issuer: String,
+    pub credential_type: String,
+    /// Paid once per certificate.
+    pub amount: u64,
+    /// The policy's unspent share of the reward pool; rewards stop when it
+    /// cannot cover `amount`.
+    pub budget: u64,
+}
+
+/// A reward paid to a certificate's holder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateReward {
+    pub amount: u64,
+    /// Issuer whose policy funded the reward.
+    pub paid_by: String,
+    pub rewarded_at: DateTime<Utc>,
+    pub transaction_id: String,
+    /// Set once the reward transaction is mined.
+    pub anchor: Option<BlockAnchor>,
+}
+
 /// Represents a simple on-chain certificate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Certificate {
     pub id: String,
     pub issued_to: String,
     pub description: String,
-    pub verified: bool,
+    /// Wallet address of the issuing institution. Empty for certificates issued
+    /// before issuers were recorded.
+    #[serde(default)]
+    pub issuer: String,
+    /// Kind of credential, e.g. `degree`, `course_completion`.
+    #[serde(default)]
+    pub credential_type: String,
+    #[serde(default = "legacy_issued_at")]
+    pub issued_at: DateTime<Utc>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    /// Hex-encoded SHA-256 of the off-chain certificate document.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Set once the issuing transaction is mined.
+    #[serde(default)]
+    pub anchor: Option<BlockAnchor>,
+    #[serde(default)]
+    pub status: CertificateStatus,
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
+    /// Issuer signature over [`Certificate::signing_payload`].
+    #[serde(default)]
+    pub signature: Option<IssuerSignature>,
+    /// Set for certificates issued through a batch.
+    #[serde(default)]
+    pub batch: Option<BatchMembership>,
+    /// Set once the holder has been rewarded; a certificate is rewarded at most once.
+    #[serde(default)]
+    pub reward: Option<CertificateReward>,
+}
+
+fn legacy_issued_at() -> DateTime<Utc> {
+    DateTime::UNIX_EPOCH
+}
+
+impl Certificate {
+    pub fn new(id: &str, issued_to: &str, description: &str, issuer: &str) -> Self {
+        Certificate {
+            id: id.to_string(),
+            issued_to: issued_to.to_string(),
+            description: description.to_string(),
+            issuer: issuer.to_string(),
+            credential_type: String::new(),
+            issued_at: Utc::now(),
+            expires_at: None,
+            metadata: serde_json::Map::new(),
+            content_hash: None,
+            anchor: None,
+            status: CertificateStatus::Active,
+            status_history: Vec::new(),
+            signature: None,
+            batch: None,
+            reward: None,
+        }
+    }
+
+    /// `None` unless the certificate was batch-issued.
+    pub fn receipt(&self) -> Option<CertificateReceipt> {
+        let batch = self.batch.as_ref()?;
+        Some(CertificateReceipt {
+            certificate_id: self.id.clone(),
+            payload: self.signing_payload(),
+            target_hash: hex::encode(self.leaf_hash()),
+            merkle_root: batch.root.clone(),
+            proof: batch.proof.clone(),
+            anchor: self.anchor.clone(),
+        })
+    }
+
+    /// Merkle leaf for batch issuance: SHA-256 of the signing payload.
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        merkle::hash_leaf(self.signing_payload().as_bytes())
+    }
+
+    /// Canonical bytes issuers sign: compact JSON with sorted keys covering
+    /// everything the issuer asserts. Node-assigned fields (`issued_at`,
+    /// status, anchor) are not part of it.
+    pub fn signing_payload(&self) -> String {
+        serde_json::json!({
+            "content_hash": self.content_hash,
+            "credential_type": self.credential_type,
+            "description": self.description,
+            "expires_at": self.expires_at,
+            "id": self.id,
+            "issued_to": self.issued_to,
+            "issuer": self.issuer.to_lowercase(),
+            "metadata": self.metadata,
+        })
+        .to_string()
+    }
+
+    pub fn check_signature(&self, issuers: &IssuerRegistry) -> SignatureCheck {
+        let trusted = issuers.get(&self.issuer);
+        let mut check = SignatureCheck {
+            signature_valid: false,
+            trusted_issuer: trusted.is_some(),
+            scheme: self.signature.as_ref().map(|signature| signature.scheme),
+            signer: None,
+            message: String::new(),
+        };
+        let Some(signature) = &self.signature else {
+            check.message = "Certificate is not signed".to_string();
+            return check;
+        };
+
+        let payload = self.signing_payload();
+        match signature.scheme {
+            SignatureScheme::Ethereum => {
+                match recover_address_from_signature(&payload, &signature.signature) {
+                    Ok(signer) => {
+                        check.signature_valid = signer.eq_ignore_ascii_case(&self.issuer);
+                        check.signer = Some(signer);
+                    }
+                    Err(err) => {
+                        check.message = format!("Invalid signature: {}", err);
+                        return check;
+                    }
+                }
+            }
+            SignatureScheme::Secp256k1 => {
+                let Some(public_key) = trusted.and_then(|issuer| issuer.public_key.as_ref()) else {
+                    check.message = "No trusted public key registered for issuer".to_string();
+                    return check;
+                };
+                let digest = hex::encode(Sha256::digest(payload.as_bytes()));
+                let signature_hex = signature
+                    .signature
+                    .strip_prefix("0x")
+                    .unwrap_or(&signature.signature);
+                check.signature_valid =
+                    pos::verify_block_signature(&digest, public_key, signature_hex);
+                check.signer = Some(public_key.clone());
+            }
+        }
+
+        check.message = match (check.signature_valid, check.trusted_issuer) {
+            (true, true) => "Signature valid and issuer trusted",
+            (true, false) => "Signature valid but issuer is not trusted",
+            (false, _) => "Signature was not made by the certificate issuer",
+        }
+        .to_string();
+        check
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// The recorded status, or `Expired` for an active certificate past its expiry.
+    pub fn effective_status(&self, now: DateTime<Utc>) -> CertificateStatus {
+        if self.status == CertificateStatus::Active && self.is_expired(now) {
+            CertificateStatus::Expired
+        } else {
+            self.status
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), CertificateError> {
+        if self.id.trim().is_empty() {
+            return Err(CertificateError::Invalid(
+                "Certificate id cannot be empty".to_string(),
+            ));
+        }
+        if self.issued_to.trim().is_empty() {
+            return Err(CertificateError::Invalid(
+                "Certificate holder cannot be empty".to_string(),
+            ));
+        }
+        if self.issuer.trim().is_empty() {
+            return Err(CertificateError::Invalid(
+                "Certificate issuer cannot be empty".to_string(),
+            ));
+        }
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= self.issued_at)
+        {
+            return Err(CertificateError::Invalid(
+                "Certificate expiry must be after its issue date".to_string(),
+            ));
+        }
+        if let Some(content_hash) = &self.content_hash {
+            if content_hash.len() != 64 || !content_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(CertificateError::Invalid(
+                    "content_hash must be a hex-encoded SHA-256 digest".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Filters for [`ContractExecutor::query_certificates`]. Addresses match case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct CertificateQuery {
+    pub holder: Option<String>,
+    pub issuer: Option<String>,
+    /// Matched against the effective status, so `expired` works too.
+    pub status: Option<CertificateStatus>,
+}
+
+/// Secondary indexes over certificate ids. Derived from `certificates`, so they
+/// are not persisted and are rebuilt on load.
+#[derive(Debug, Clone, Default)]
+struct CertificateIndexes {
+    by_holder: HashMap<String, BTreeSet<String>>,
+    by_issuer: HashMap<String, BTreeSet<String>>,
+    by_status: HashMap<CertificateStatus, BTreeSet<String>>,
+}
+
+impl CertificateIndexes {
+    fn insert(&mut self, cert: &Certificate) {
+        self.by_holder
+            .entry(cert.issued_to.to_lowercase())
+            .or_default()
+            .insert(cert.id.clone());
+        self.by_issuer
+            .entry(cert.issuer.to_lowercase())
+            .or_default()
+            .insert(cert.id.clone());
+        self.by_status
+            .entry(cert.status)
+            .or_default()
+            .insert(cert.id.clone());
+    }
+
+    fn move_status(&mut self, id: &str, from: CertificateStatus, to: CertificateStatus) {
+        if let Some(ids) = self.by_status.get_mut(&from) {
+            ids.remove(id);
+        }
+        self.by_status.entry(to).or_default().insert(id.to_string());
+    }
 }
 
 /// The contract executor that manages certificates and token issuance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractExecutor {
     pub certificates: HashMap<String, Certificate>,
+    /// Batches keyed by hex Merkle root.
+    #[serde(default)]
+    pub batches: HashMap<String, CertificateBatch>,
+    /// Keyed by lowercased issuer and credential type, see `reward_policy_key`.
+    #[serde(default)]
+    pub reward_policies: HashMap<String, RewardPolicy>,
+    /// Deployed WASM contracts keyed by address.
+    #[serde(default)]
+    pub wasm_contracts: HashMap<String, WasmContract>,
+    #[serde(skip)]
+    indexes: CertificateIndexes,
+    /// Logs emitted since the last `take_logs`, for the current transaction's receipt.
+    #[serde(skip)]
+    pub(crate) logs: Vec<EventLog>,
+    #[serde(skip)]
+    pub(super) runtime: Runtime,
+}
+
+/// A unit enum's serde name, e.g. `issued_in_error`, for log attributes.
+fn serde_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn reward_policy_key(issuer: &str, credential_type: &str) -> String {
+    format!("{}/{}", issuer.to_lowercase(), credential_type)
+}
+
+impl Default for ContractExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ContractExecutor {
     pub fn new() -> Self {
         ContractExecutor {
             certificates: HashMap::new(),
+            batches: HashMap::new(),
+            reward_policies: HashMap::new(),
+            wasm_contracts: HashMap::new(),
+            indexes: CertificateIndexes::default(),
+            logs: Vec::new(),
+            runtime: Runtime::default(),
         }
     }
 
-    /// Issues a new certificate
-    pub fn issue_certificate(&mut self, id: &str, issued_to: &str, description: &str) {
-        let cert = Certificate {
-            id: id.to_string(),
-            issued_to: issued_to.to_string(),
-            description: description.to_string(),
-            verified: false,
+    /// Rebuilds the secondary indexes, e.g. after loading a snapshot.
+    pub fn rebuild_indexes(&mut self) {
+        self.indexes = CertificateIndexes::default();
+        for cert in self.certificates.values() {
+            self.indexes.insert(cert);
+        }
+    }
+
+    /// Drains the logs emitted since the last call.
+    pub fn take_logs(&mut self) -> Vec<EventLog> {
+        std::mem::take(&mut self.logs)
+    }
+
+    fn insert_certificate(&mut self, cert: Certificate) {
+        self.logs.push(
+            EventLog::new(CERTIFICATE_CONTRACT, "CertificateIssued")
+                .with("id", &cert.id)
+                .with("holder", &cert.issued_to)
+                .with("issuer", &cert.issuer)
+                .with("credential_type", &cert.credential_type),
+        );
+        self.indexes.insert(&cert);
+        self.certificates.insert(cert.id.clone(), cert);
+    }
+
+    /// Certificates matching every given filter, ordered by id.
+    pub fn query_certificates(
+        &self,
+        query: &CertificateQuery,
+        now: DateTime<Utc>,
+    ) -> Vec<&Certificate> {
+        let lookup = |index: &HashMap<String, BTreeSet<String>>, key: &str| {
+            index.get(&key.to_lowercase()).cloned().unwrap_or_default()
         };
-        self.certificates.insert(id.to_string(), cert);
-        println!("Certificate {} issued to {}", id, issued_to);
+        // Expired certificates are stored as active
+        let stored_status = query.status.map(|status| match status {
+            CertificateStatus::Expired => CertificateStatus::Active,
+            status => status,
+        });
+
+        let mut candidates: Option<BTreeSet<String>> = None;
+        let mut narrow = |ids: BTreeSet<String>| {
+            candidates = Some(match candidates.take() {
+                Some(current) => current.intersection(&ids).cloned().collect(),
+                None => ids,
+            });
+        };
+        if let Some(holder) = &query.holder {
+            narrow(lookup(&self.indexes.by_holder, holder));
+        }
+        if let Some(issuer) = &query.issuer {
+            narrow(lookup(&self.indexes.by_issuer, issuer));
+        }
+        if let Some(status) = stored_status {
+            narrow(
+                self.indexes
+                    .by_status
+                    .get(&status)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+        }
+        let ids = candidates.unwrap_or_else(|| self.certificates.keys().cloned().collect());
+
+        ids.iter()
+            .filter_map(|id| self.certificates.get(id))
+            .filter(|cert| {
+                query
+                    .status
+                    .is_none_or(|status| cert.effective_status(now) == status)
+            })
+            .collect()
     }
 
-    /// Verifies a certificate by ID
-    pub fn verify_certificate(&mut self, id: &str) -> bool {
-        if let Some(cert) = self.certificates.get_mut(id) {
-            cert.verified = true;
-            println!("Certificate {} verified!", id);
-            true
-        } else {
-            println!("Certificate {} not found.", id);
-            false
-        }
-    }
-
-    /// Issues tokens as a reward for certificate verification
-    pub fn reward_certificate_holder(&self, token: &mut Token, cert_id: &str, amount: u64) -> bool {
-        if let Some(cert) = self.certificates.get(cert_id) {
-            if cert.verified {
-                token.mint(&cert.issued_to, amount);
-                println!(
-                    "Rewarded {} {} tokens for certificate {}",
-                    cert.issued_to, amount, cert_id
-                );
-                return true;
-            } else {
-                println!("Certificate {} is not verified yet.", cert_id);
+    /// Issues a set of certificates under one Merkle root and gives each its
+    /// inclusion proof. All-or-nothing: one invalid or duplicate certificate
+    /// rejects the whole batch.
+    pub fn issue_batch(
+        &mut self,
+        mut certs: Vec<Certificate>,
+        issuer: &str,
+    ) -> Result<CertificateBatch, CertificateError> {
+        let mut ids = std::collections::HashSet::new();
+        for cert in &certs {
+            cert.validate()?;
+            if !cert.issuer.eq_ignore_ascii_case(issuer) {
+                return Err(CertificateError::IssuerMismatch(cert.id.clone()));
             }
-        } else {
-            println!("Certificate {} not found.", cert_id);
+            if self.certificates.contains_key(&cert.id) || !ids.insert(cert.id.as_str()) {
+                return Err(CertificateError::AlreadyExists(cert.id.clone()));
+            }
+        }
+
+        let leaves: Vec<[u8; 32]> = certs.iter().map(Certificate::leaf_hash).collect();
+        let root = merkle::merkle_root(&leaves)
+            .map(hex::encode)
+            .ok_or(CertificateError::EmptyBatch)?;
+        if self.batches.contains_key(&root) {
+            return Err(CertificateError::BatchExists(root));
+        }
+
+        for (index, cert) in certs.iter_mut().enumerate() {
+            cert.batch = Some(BatchMembership {
+                root: root.clone(),
+                proof: merkle::merkle_proof(&leaves, index).unwrap_or_default(),
+            });
+        }
+        let batch = CertificateBatch {
+            root: root.clone(),
+            issuer: issuer.to_string(),
+            certificate_ids: certs.iter().map(|cert| cert.id.clone()).collect(),
+            created_at: Utc::now(),
+            anchor: None,
+        };
+        for cert in certs {
+            self.insert_certificate(cert);
+        }
+        self.logs.push(
+            EventLog::new(CERTIFICATE_CONTRACT, "CertificateBatchIssued")
+                .with("root", &root)
+                .with("issuer", issuer)
+                .with("count", batch.certificate_ids.len()),
+        );
+        self.batches.insert(root, batch.clone());
+        Ok(batch)
+    }
+
+    /// Records the block that anchored a batch root on the batch and its certificates.
+    pub fn anchor_batch(&mut self, root: &str, anchor: BlockAnchor) -> bool {
+        let Some(batch) = self.batches.get_mut(root) else {
+            return false;
+        };
+        batch.anchor = Some(anchor.clone());
+        for id in &batch.certificate_ids {
+            if let Some(cert) = self.certificates.get_mut(id) {
+                cert.anchor = Some(anchor.clone());
+            }
+        }
+        true
+    }
+
+    /// Issues a new certificate. Ids are unique; reissuing an existing id is rejected.
+    pub fn issue_certificate(&mut self, cert: Certificate) -> Result<(), CertificateError> {
+        cert.validate()?;
+        if self.certificates.contains_key(&cert.id) {
+            return Err(CertificateError::AlreadyExists(cert.id.clone()));
+        }
+        self.insert_certificate(cert);
+        Ok(())
+    }
+
+    /// Records the block that anchored one of a certificate's transactions:
+    /// its reward or a status change if the transaction id matches one,
+    /// otherwise the issuance.
+    pub fn anchor_certificate(&mut self, id: &str, anchor: BlockAnchor) -> bool {
+        let Some(cert) = self.certificates.get_mut(id) else {
+            return false;
+        };
+        if let Some(reward) = cert
+            .reward
+            .as_mut()
+            .filter(|reward| reward.transaction_id == anchor.transaction_id)
+        {
+            reward.anchor = Some(anchor);
+            return true;
+        }
+        match cert
+            .status_history
+            .iter_mut()
+            .find(|change| change.transaction_id == anchor.transaction_id)
+        {
+            Some(change) => change.anchor = Some(anchor),
+            None => cert.anchor = Some(anchor),
+        }
+        true
+    }
+
+    /// Moves a certificate to a new status. Allowed transitions are
+    /// active -> suspended, suspended -> active, and active/suspended -> revoked.
+    pub fn change_status(
+        &mut self,
+        id: &str,
+        status: CertificateStatus,
+        reason: StatusReason,
+        note: Option<String>,
+        changed_by: &str,
+        transaction_id: &str,
+    ) -> Result<StatusChange, CertificateError> {
+        let cert = self
+            .certificates
+            .get_mut(id)
+            .ok_or_else(|| CertificateError::NotFound(id.to_string()))?;
+
+        let allowed = matches!(
+            (cert.status, status),
+            (CertificateStatus::Active, CertificateStatus::Suspended)
+                | (CertificateStatus::Suspended, CertificateStatus::Active)
+                | (CertificateStatus::Active, CertificateStatus::Revoked)
+                | (CertificateStatus::Suspended, CertificateStatus::Revoked)
+        );
+        if !allowed {
+            return Err(CertificateError::InvalidTransition {
+                id: id.to_string(),
+                from: cert.status,
+                to: status,
+            });
+        }
+
+        let change = StatusChange {
+            status,
+            reason,
+            note,
+            changed_by: changed_by.to_string(),
+            changed_at: Utc::now(),
+            transaction_id: transaction_id.to_string(),
+            anchor: None,
+        };
+        let previous = cert.status;
+        cert.status = status;
+        cert.status_history.push(change.clone());
+        self.indexes.move_status(id, previous, status);
+        self.logs.push(
+            EventLog::new(CERTIFICATE_CONTRACT, "CertificateStatusChanged")
+                .with("id", id)
+                .with("holder", &cert.issued_to)
+                .with("issuer", &cert.issuer)
+                .with("status", serde_name(&status))
+                .with("reason", serde_name(&reason)),
+        );
+        Ok(change)
+    }
+
+    pub fn revoke_certificate(
+        &mut self,
+        id: &str,
+        reason: StatusReason,
+        note: Option<String>,
+        changed_by: &str,
+        transaction_id: &str,
+    ) -> Result<StatusChange, CertificateError> {
+        self.change_status(
+            id,
+            CertificateStatus::Revoked,
+            reason,
+            note,
+            changed_by,
+            transaction_id,
+        )
+    }
+
+    pub fn suspend_certificate(
+        &mut self,
+        id: &str,
+        reason: StatusReason,
+        note: Option<String>,
+        changed_by: &str,
+        transaction_id: &str,
+    ) -> Result<StatusChange, CertificateError> {
+        self.change_status(
+            id,
+            CertificateStatus::Suspended,
+            reason,
+            note,
+            changed_by,
+            transaction_id,
+        )
+    }
+
+    /// Certificates that are currently revoked or suspended, for revocation lists.
+    pub fn revoked_or_suspended(&self) -> Vec<&Certificate> {
+        let mut certificates: Vec<&Certificate> = self
+            .certificates
+            .values()
+            .filter(|cert| {
+                matches!(
+                    cert.status,
+                    CertificateStatus::Revoked | CertificateStatus::Suspended
+                )
+            })
+            .collect();
+        certificates.sort_by(|a, b| a.id.cmp(&b.id));
+        certificates
+    }
+
+    /// A certificate verifies when it is active and carries a valid signature
+    /// from a trusted issuer. Verification does not change any state.
+    pub fn verify_certificate(
+        &self,
+        id: &str,
+        issuers: &IssuerRegistry,
+    ) -> Result<(), CertificateError> {
+        let cert = self
+            .certificates
+            .get(id)
+            .ok_or_else(|| CertificateError::NotFound(id.to_string()))?;
+        let status = cert.effective_status(Utc::now());
+        if status != CertificateStatus::Active {
+            return Err(CertificateError::NotActive {
+                id: id.to_string(),
+                status,
+            });
+        }
+        let check = cert.check_signature(issuers);
+        if !check.is_valid() {
+            return Err(CertificateError::UntrustedSignature {
+                id: id.to_string(),
+                reason: check.message,
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds or replaces the issuer's reward policy for a credential type,
+    /// moving its budget from the issuer into the reward pool. A replaced
+    /// policy's unspent budget goes back to its issuer first.
+    pub fn set_reward_policy(
+        &mut self,
+        token: &mut Token,
+        policy: RewardPolicy,
+    ) -> Result<(), CertificateError> {
+        if policy.issuer.trim().is_empty() {
+            return Err(CertificateError::InvalidRewardPolicy(
+                "Reward policy issuer cannot be empty".to_string(),
+            ));
+        }
+        if policy.amount == 0 {
+            return Err(CertificateError::InvalidRewardPolicy(
+                "Reward amount must be greater than zero".to_string(),
+            ));
+        }
+        let key = reward_policy_key(&policy.issuer, &policy.credential_type);
+        let refund = self
+            .reward_policies
+            .get(&key)
+            .map(|replaced| (replaced.issuer.clone(), replaced.budget));
+        if let Some((issuer, budget)) = &refund {
+            token.transfer(REWARD_POOL_ACCOUNT, issuer, *budget)?;
+        }
+        if let Err(err) = token.transfer(&policy.issuer, REWARD_POOL_ACCOUNT, policy.budget) {
+            if let Some((issuer, budget)) = &refund {
+                let _ = token.transfer(issuer, REWARD_POOL_ACCOUNT, *budget);
+            }
+            return Err(err.into());
         }
-        false
+        self.reward_policies.insert(key, policy);
+        Ok(())
+    }
+
+    pub fn list_reward_policies(&self) -> Vec<&RewardPolicy> {
+        let mut policies: Vec<&RewardPolicy> = self.reward_policies.values().collect();
+        policies
+            .sort_by(|a, b| (&a.issuer, &a.credential_type).cmp(&(&b.issuer, &b.credential_type)));
+        policies
+    }
+
+    /// Pays a verified certificate's holder under its issuer's reward policy,
+    /// from the budget escrowed in the reward pool rather than by minting.
+    /// Returns the `Reward` transaction recording the payment, or `None` when
+    /// there is no policy or the holder was already rewarded.
+    pub fn reward_certificate_holder(
+        &mut self,
+        token: &mut Token,
+        cert_id: &str,
+    ) -> Result<Option<Transaction>, CertificateError> {
+        let cert = self
+            .certificates
+            .get_mut(cert_id)
+            .ok_or_else(|| CertificateError::NotFound(cert_id.to_string()))?;
+        if cert.reward.is_some() {
+            return Ok(None);
+        }
+        let status = cert.effective_status(Utc::now());
+        if status != CertificateStatus::Active {
+            return Err(CertificateError::NotActive {
+                id: cert_id.to_string(),
+                status,
+            });
+        }
+        let Some(policy) = self
+            .reward_policies
+            .get_mut(&reward_policy_key(&cert.issuer, &cert.credential_type))
+        else {
+            return Ok(None);
+        };
+        if policy.budget < policy.amount {
+            return Err(CertificateError::RewardBudgetExhausted(
+                policy.credential_type.clone(),
+            ));
+        }
+        token.transfer(REWARD_POOL_ACCOUNT, &cert.issued_to, policy.amount)?;
+        policy.budget -= policy.amount;
+
+        let transaction = Transaction::new(
+            Some(cert.issuer.clone()),
+            cert.issued_to.clone(),
+            policy.amount,
+            TransactionType::Reward,
+        )
+        .with_reference(cert_id);
+        cert.reward = Some(CertificateReward {
+            amount: policy.amount,
+            paid_by: cert.issuer.clone(),
+            rewarded_at: transaction.timestamp,
+            transaction_id: transaction.id.clone(),
+            anchor: None,
+        });
+        self.logs.push(EventLog::transfer(
+            &token.symbol,
+            REWARD_POOL_ACCOUNT,
+            &cert.issued_to,
+            policy.amount,
+        ));
+        self.logs.push(
+            EventLog::new(CERTIFICATE_CONTRACT, "CertificateRewarded")
+                .with("id", cert_id)
+                .with("holder", &cert.issued_to)
+                .with("issuer", &cert.issuer)
+                .with("amount", policy.amount),
+        );
+        Ok(Some(transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::issuers::TrustedIssuer;
+    use chrono::Duration;
+
+    #[test]
+    fn issues_validated_certificates_once() {
+        let mut contracts = ContractExecutor::new();
+        let mut cert = Certificate::new("CERT001", "Alice", "BSc Computer Science", "0xuni");
+        cert.credential_type = "degree".to_string();
+        cert.content_hash = Some("ab".repeat(32));
+        assert!(contracts.issue_certificate(cert.clone()).is_ok());
+        assert!(contracts.issue_certificate(cert.clone()).is_err());
+
+        let mut expired = Certificate::new("CERT002", "Bob", "Course", "0xuni");
+        expired.expires_at = Some(expired.issued_at - Duration::days(1));
+        assert!(contracts.issue_certificate(expired).is_err());
+
+        let anchor = BlockAnchor {
+            block_index: 3,
+            block_hash: "00abc".to_string(),
+            transaction_id: "tx-1".to_string(),
+        };
+        assert!(contracts.anchor_certificate("CERT001", anchor.clone()));
+        assert_eq!(contracts.certificates["CERT001"].anchor, Some(anchor));
+    }
+
+    // Well-known test key 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318.
+    const ISSUER_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const ISSUER_ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+    fn signed_certificate() -> (IssuerRegistry, Certificate) {
+        let secp = secp256k1::Secp256k1::new();
+        let secret = secp256k1::SecretKey::from_slice(&hex::decode(ISSUER_KEY).unwrap()).unwrap();
+        let public_key = hex::encode(secret.public_key(&secp).serialize());
+
+        let mut issuers = IssuerRegistry::default();
+        issuers
+            .register(TrustedIssuer {
+                address: ISSUER_ADDRESS.to_string(),
+                public_key: Some(public_key),
+                name: Some("Test University".to_string()),
+                added_by: "0xadmin".to_string(),
+                added_at: Utc::now(),
+            })
+            .unwrap();
+
+        let mut cert = Certificate::new("CERT001", "Alice", "Degree", ISSUER_ADDRESS);
+        let digest = hex::encode(Sha256::digest(cert.signing_payload().as_bytes()));
+        cert.signature = Some(IssuerSignature {
+            scheme: SignatureScheme::Secp256k1,
+            signature: pos::sign_block_hash(&digest, ISSUER_KEY).unwrap(),
+        });
+        (issuers, cert)
+    }
+
+    #[test]
+    fn checks_issuer_signatures() {
+        let (issuers, cert) = signed_certificate();
+        assert!(cert.check_signature(&issuers).is_valid());
+        assert!(!cert.check_signature(&IssuerRegistry::default()).is_valid());
+
+        let mut tampered = cert.clone();
+        tampered.issued_to = "Mallory".to_string();
+        assert!(!tampered.check_signature(&issuers).signature_valid);
+
+        let unsigned = Certificate::new("CERT002", "Bob", "Degree", ISSUER_ADDRESS);
+        assert!(!unsigned.check_signature(&issuers).signature_valid);
+    }
+
+    #[test]
+    fn batches_anchor_every_certificate_with_a_proof() {
+        let mut contracts = ContractExecutor::new();
+        let certs: Vec<Certificate> = (0..5)
+            .map(|i| Certificate::new(&format!("GRAD{}", i), "Student", "BSc", "0xuni"))
+            .collect();
+        let mut duplicate = certs.clone();
+        duplicate.push(certs[0].clone());
+        assert!(contracts.issue_batch(duplicate, "0xuni").is_err());
+
+        let batch = contracts.issue_batch(certs, "0xuni").unwrap();
+        assert_eq!(contracts.certificates.len(), 5);
+        let anchor = BlockAnchor {
+            block_index: 1,
+            block_hash: "00ab".to_string(),
+            transaction_id: "tx-1".to_string(),
+        };
+        assert!(contracts.anchor_batch(&batch.root, anchor.clone()));
+
+        let cert = &contracts.certificates["GRAD3"];
+        let mut receipt = cert.receipt().unwrap();
+        assert_eq!(receipt.merkle_root, batch.root);
+        assert!(receipt.proof_is_valid());
+        assert_eq!(cert.anchor, Some(anchor));
+
+        receipt.payload = receipt.payload.replace("Student", "Mallory");
+        assert!(!receipt.proof_is_valid());
+    }
+
+    #[test]
+    fn suspends_reinstates_and_revokes() {
+        let (issuers, cert) = signed_certificate();
+        let mut contracts = ContractExecutor::new();
+        contracts.issue_certificate(cert).unwrap();
+
+        assert!(contracts.verify_certificate("CERT001", &issuers).is_ok());
+        contracts
+            .suspend_certificate(
+                "CERT001",
+                StatusReason::UnderInvestigation,
+                None,
+                "0xuni",
+                "tx-1",
+            )
+            .unwrap();
+        assert!(contracts.verify_certificate("CERT001", &issuers).is_err());
+        contracts
+            .change_status(
+                "CERT001",
+                CertificateStatus::Active,
+                StatusReason::Resolved,
+                None,
+                "0xuni",
+                "tx-2",
+            )
+            .unwrap();
+        assert!(contracts.verify_certificate("CERT001", &issuers).is_ok());
+
+        contracts
+            .revoke_certificate("CERT001", StatusReason::Fraud, None, "0xuni", "tx-3")
+            .unwrap();
+        assert!(contracts.verify_certificate("CERT001", &issuers).is_err());
+        assert!(contracts
+            .suspend_certificate("CERT001", StatusReason::Other, None, "0xuni", "tx-4")
+            .is_err());
+
+        let cert = &contracts.certificates["CERT001"];
+        assert_eq!(cert.status_history.len(), 3);
+        assert_eq!(contracts.revoked_or_suspended().len(), 1);
+        let topics: Vec<String> = contracts
+            .take_logs()
+            .into_iter()
+            .map(|log| log.topic)
+            .collect();
+        assert_eq!(topics[0], "CertificateIssued");
+        assert_eq!(topics.len(), 4);
+        assert!(contracts.take_logs().is_empty());
+
+        let revoked = CertificateQuery {
+            holder: Some("alice".to_string()),
+            status: Some(CertificateStatus::Revoked),
+            ..CertificateQuery::default()
+        };
+        assert_eq!(contracts.query_certificates(&revoked, Utc::now()).len(), 1);
+        let active = CertificateQuery {
+            status: Some(CertificateStatus::Active),
+            ..CertificateQuery::default()
+        };
+        assert!(contracts.query_certificates(&active, Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn rewards_holders_once_from_the_escrowed_budget() {
+        let mut contracts = ContractExecutor::new();
+        let mut token = Token::new("Hikma", "HKM", 100, "0xuni");
+        for id in ["CERT001", "CERT002", "CERT003"] {
+            let mut cert = Certificate::new(id, id, "Course", "0xuni");
+            cert.credential_type = "course".to_string();
+            contracts.issue_certificate(cert).unwrap();
+        }
+        assert!(matches!(
+            contracts.reward_certificate_holder(&mut token, "CERT001"),
+            Ok(None)
+        ));
+
+        let policy = |budget| RewardPolicy {
+            issuer: "0xuni".to_string(),
+            credential_type: "course".to_string(),
+            amount: 10,
+            budget,
+        };
+        // The budget must be covered by the issuer's balance
+        assert!(contracts
+            .set_reward_policy(&mut token, policy(200))
+            .is_err());
+        contracts.set_reward_policy(&mut token, policy(50)).unwrap();
+        // Replacing the policy refunds the old budget before escrowing the new one
+        contracts.set_reward_policy(&mut token, policy(20)).unwrap();
+        assert!(contracts
+            .set_reward_policy(&mut token, policy(200))
+            .is_err());
+        assert_eq!(contracts.reward_policies["0xuni/course"].budget, 20);
+        assert_eq!(token.balance_of("0xuni"), 80);
+        assert_eq!(token.balance_of(REWARD_POOL_ACCOUNT), 20);
+
+        let tx = contracts
+            .reward_certificate_holder(&mut token, "CERT001")
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.amount, 10);
+        assert_eq!(tx.reference.as_deref(), Some("CERT001"));
+        assert_eq!(token.balance_of("CERT001"), 10);
+        assert_eq!(token.balance_of(REWARD_POOL_ACCOUNT), 10);
+        assert_eq!(token.total_supply, 100);
+        assert!(matches!(
+            contracts.reward_certificate_holder(&mut token, "CERT001"),
+            Ok(None)
+        ));
+
+        let anchor = BlockAnchor {
+            block_index: 2,
+            block_hash: "00ab".to_string(),
+            transaction_id: tx.id.clone(),
+        };
+        contracts.anchor_certificate("CERT001", anchor.clone());
+        let cert = &contracts.certificates["CERT001"];
+        assert_eq!(cert.reward.as_ref().unwrap().anchor, Some(anchor));
+        assert!(cert.anchor.is_none());
+
+        contracts
+            .reward_certificate_holder(&mut token, "CERT002")
+            .unwrap();
+        assert!(contracts
+            .reward_certificate_holder(&mut token, "CERT003")
+            .is_err());
+        assert_eq!(token.balance_of("CERT003"), 0);
+        assert_eq!(token.balance_of(REWARD_POOL_ACCOUNT), 0);
+        assert_eq!(token.balance_of("0xuni"), 80);
+    }
+
+    #[test]
+    fn loads_legacy_certificates() {
+        let legacy = r#"{"id":"C1","issued_to":"Alice","description":"d","verified":true}"#;
+        let cert: Certificate = serde_json::from_str(legacy).unwrap();
+        assert!(cert.issuer.is_empty());
+        assert!(cert.anchor.is_none());
+        assert_eq!(cert.status, CertificateStatus::Active);
+        assert!(!cert.is_expired(Utc::now()));
     }
 }
@@ -0,0 +1,195 @@
+// src/contract/credential.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::contract::{BlockAnchor, Certificate, IssuerSignature, SignatureScheme};
+use crate::auth::siwe::is_ethereum_address;
+
+const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+const CREDENTIAL_TYPE: &str = "VerifiableCredential";
+const CERTIFICATE_CREDENTIAL_TYPE: &str = "HikmalayerCertificate";
+const PROOF_TYPE: &str = "HikmalayerAnchoredSignature";
+const CERTIFICATE_URN_PREFIX: &str = "urn:hikmalayer:certificate:";
+const DID_ETHR_PREFIX: &str = "did:ethr:";
+
+/// A certificate as a W3C Verifiable Credential (Data Model 2.0) document.
+/// Terms outside the base context fall back to its `@vocab`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    pub issuer: String,
+    pub valid_from: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+    pub credential_subject: CredentialSubject,
+    pub proof: CredentialProof,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSubject {
+    /// `did:ethr` of the holder when the certificate was issued to a wallet address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub issued_to: String,
+    pub description: String,
+    #[serde(default)]
+    pub credential_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The issuer signature over the certificate's signing payload, plus the block
+/// that anchored the issuing transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub created: DateTime<Utc>,
+    pub proof_purpose: String,
+    pub verification_method: String,
+    pub signature_scheme: SignatureScheme,
+    pub proof_value: String,
+    pub anchor: ProofAnchor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofAnchor {
+    pub block_index: u64,
+    pub block_hash: String,
+    pub transaction_id: String,
+}
+
+impl VerifiableCredential {
+    /// Exports a signed, anchored certificate.
+    pub fn from_certificate(cert: &Certificate) -> Result<Self, String> {
+        let signature = cert
+            .signature
+            .as_ref()
+            .ok_or("Certificate must be signed by its issuer before export")?;
+        let anchor = cert
+            .anchor
+            .as_ref()
+            .ok_or("Certificate is not anchored on-chain yet")?;
+        let issuer = format!("{}{}", DID_ETHR_PREFIX, cert.issuer.to_lowercase());
+
+        Ok(VerifiableCredential {
+            context: vec![CREDENTIALS_V2_CONTEXT.to_string()],
+            id: format!("{}{}", CERTIFICATE_URN_PREFIX, cert.id),
+            types: vec![
+                CREDENTIAL_TYPE.to_string(),
+                CERTIFICATE_CREDENTIAL_TYPE.to_string(),
+            ],
+            issuer: issuer.clone(),
+            valid_from: cert.issued_at,
+            valid_until: cert.expires_at,
+            credential_subject: CredentialSubject {
+                id: is_ethereum_address(&cert.issued_to)
+                    .then(|| format!("{}{}", DID_ETHR_PREFIX, cert.issued_to.to_lowercase())),
+                issued_to: cert.issued_to.clone(),
+                description: cert.description.clone(),
+                credential_type: cert.credential_type.clone(),
+                content_hash: cert.content_hash.clone(),
+                metadata: cert.metadata.clone(),
+            },
+            proof: CredentialProof {
+                proof_type: PROOF_TYPE.to_string(),
+                created: cert.issued_at,
+                proof_purpose: "assertionMethod".to_string(),
+                verification_method: format!("{}#controller", issuer),
+                signature_scheme: signature.scheme,
+                proof_value: signature.signature.clone(),
+                anchor: ProofAnchor {
+                    block_index: anchor.block_index,
+                    block_hash: anchor.block_hash.clone(),
+                    transaction_id: anchor.transaction_id.clone(),
+                },
+            },
+        })
+    }
+
+    /// Rebuilds the certificate a credential claims to represent, so its
+    /// signature can be checked. Fails on documents that are not our credentials.
+    pub fn to_certificate(&self) -> Result<Certificate, String> {
+        if !self.context.iter().any(|c| c == CREDENTIALS_V2_CONTEXT) {
+            return Err("Credential is missing the W3C credentials v2 context".to_string());
+        }
+        if !self.types.iter().any(|t| t == CREDENTIAL_TYPE) {
+            return Err("Document is not a VerifiableCredential".to_string());
+        }
+        if self.proof.proof_type != PROOF_TYPE {
+            return Err(format!("Unsupported proof type {}", self.proof.proof_type));
+        }
+        let id = self
+            .id
+            .strip_prefix(CERTIFICATE_URN_PREFIX)
+            .ok_or("Credential id is not a certificate URN")?;
+        let issuer = self
+            .issuer
+            .strip_prefix(DID_ETHR_PREFIX)
+            .filter(|address| is_ethereum_address(address))
+            .ok_or("Credential issuer must be a did:ethr identifier")?;
+
+        let subject = &self.credential_subject;
+        let mut cert = Certificate::new(id, &subject.issued_to, &subject.description, issuer);
+        cert.credential_type = subject.credential_type.clone();
+        cert.issued_at = self.valid_from;
+        cert.expires_at = self.valid_until;
+        cert.metadata = subject.metadata.clone();
+        cert.content_hash = subject.content_hash.clone();
+        cert.signature = Some(IssuerSignature {
+            scheme: self.proof.signature_scheme,
+            signature: self.proof.proof_value.clone(),
+        });
+        cert.anchor = Some(BlockAnchor {
+            block_index: self.proof.anchor.block_index,
+            block_hash: self.proof.anchor.block_hash.clone(),
+            transaction_id: self.proof.anchor.transaction_id.clone(),
+        });
+        Ok(cert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_certificates() {
+        let mut cert = Certificate::new(
+            "CERT001",
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+            "BSc Computer Science",
+            "0xAbC0000000000000000000000000000000000001",
+        );
+        cert.metadata.insert("grade".to_string(), "A".into());
+        assert!(VerifiableCredential::from_certificate(&cert).is_err());
+
+        cert.signature = Some(IssuerSignature {
+            scheme: SignatureScheme::Ethereum,
+            signature: "0x00".to_string(),
+        });
+        cert.anchor = Some(BlockAnchor {
+            block_index: 2,
+            block_hash: "00ab".to_string(),
+            transaction_id: "tx-1".to_string(),
+        });
+        let credential = VerifiableCredential::from_certificate(&cert).unwrap();
+        let json = serde_json::to_value(&credential).unwrap();
+        assert_eq!(json["type"][0], "VerifiableCredential");
+        assert_eq!(json["proof"]["anchor"]["blockIndex"], 2);
+
+        let imported = credential.to_certificate().unwrap();
+        assert_eq!(imported.signing_payload(), cert.signing_payload());
+        assert_eq!(imported.anchor, cert.anchor);
+    }
+}
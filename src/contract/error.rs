@@ -0,0 +1,126 @@
+use std::fmt;
+
+use super::contract::CertificateStatus;
+use crate::token::error::TokenError;
+
+/// Why a certificate operation was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateError {
+    Invalid(String),
+    NotFound(String),
+    AlreadyExists(String),
+    IssuerMismatch(String),
+    EmptyBatch,
+    BatchExists(String),
+    InvalidTransition {
+        id: String,
+        from: CertificateStatus,
+        to: CertificateStatus,
+    },
+    NotActive {
+        id: String,
+        status: CertificateStatus,
+    },
+    /// The issuer signature is missing, invalid or from an untrusted issuer.
+    UntrustedSignature {
+        id: String,
+        reason: String,
+    },
+    InvalidRewardPolicy(String),
+    RewardBudgetExhausted(String),
+    /// The issuer could not fund a reward.
+    Token(TokenError),
+}
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertificateError::Invalid(reason) => write!(f, "{}", reason),
+            CertificateError::NotFound(id) => write!(f, "Certificate {} not found", id),
+            CertificateError::AlreadyExists(id) => write!(f, "Certificate {} already exists", id),
+            CertificateError::IssuerMismatch(id) => {
+                write!(f, "Certificate {} has a different issuer", id)
+            }
+            CertificateError::EmptyBatch => write!(f, "A batch needs at least one certificate"),
+            CertificateError::BatchExists(root) => write!(f, "Batch {} already exists", root),
+            CertificateError::InvalidTransition { id, from, to } => write!(
+                f,
+                "Certificate {} cannot move from {:?} to {:?}",
+                id, from, to
+            ),
+            CertificateError::NotActive { id, status } => {
+                write!(f, "Certificate {} is {:?}", id, status)
+            }
+            CertificateError::UntrustedSignature { id, reason } => {
+                write!(f, "Certificate {}: {}", id, reason)
+            }
+            CertificateError::InvalidRewardPolicy(reason) => write!(f, "{}", reason),
+            CertificateError::RewardBudgetExhausted(credential_type) => write!(
+                f,
+                "Reward budget for {} certificates is exhausted",
+                credential_type
+            ),
+            CertificateError::Token(err) => write!(f, "Reward could not be paid: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CertificateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CertificateError::Token(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<TokenError> for CertificateError {
+    fn from(err: TokenError) -> Self {
+        CertificateError::Token(err)
+    }
+}
+
+/// Why a WASM contract could not be deployed or called. Failures while the
+/// contract runs are reported in its `ExecutionOutcome` instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractError {
+    CodeTooLarge { max_bytes: usize },
+    InvalidWasm(String),
+    AlreadyDeployed(String),
+    NotFound(String),
+    UnknownFunction { address: String, function: String },
+    GasLimitTooHigh { max: u64 },
+    GasLimitTooLow { min: u64 },
+    InsufficientFunds { caller: String, max_fee: u64 },
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractError::CodeTooLarge { max_bytes } => {
+                write!(f, "Contract code exceeds {} bytes", max_bytes)
+            }
+            ContractError::InvalidWasm(reason) => write!(f, "Invalid WASM: {}", reason),
+            ContractError::AlreadyDeployed(address) => {
+                write!(f, "Contract {} already exists", address)
+            }
+            ContractError::NotFound(address) => write!(f, "Contract {} not found", address),
+            ContractError::UnknownFunction { address, function } => {
+                write!(f, "Contract {} has no function {}", address, function)
+            }
+            ContractError::GasLimitTooHigh { max } => {
+                write!(f, "Gas limit cannot exceed {}", max)
+            }
+            ContractError::GasLimitTooLow { min } => {
+                write!(f, "Gas limit must be at least {}", min)
+            }
+            ContractError::InsufficientFunds { caller, max_fee } => write!(
+                f,
+                "{} cannot cover the value and a fee of up to {}",
+                caller, max_fee
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContractError {}
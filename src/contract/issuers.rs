@@ -0,0 +1,57 @@
+// src/contract/issuers.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An institution whose certificate signatures verifiers should accept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedIssuer {
+    pub address: String,
+    /// Hex-encoded secp256k1 public key (33 or 65 bytes). Only needed for
+    /// `secp256k1` signatures; Ethereum signatures are checked by address.
+    pub public_key: Option<String>,
+    pub name: Option<String>,
+    pub added_by: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Trusted issuers keyed by lowercased wallet address, managed by admins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssuerRegistry {
+    issuers: HashMap<String, TrustedIssuer>,
+}
+
+impl IssuerRegistry {
+    /// Adds or replaces an issuer entry.
+    pub fn register(&mut self, issuer: TrustedIssuer) -> Result<(), String> {
+        if issuer.address.trim().is_empty() {
+            return Err("Issuer address cannot be empty".to_string());
+        }
+        if let Some(public_key) = &issuer.public_key {
+            let valid = hex::decode(public_key)
+                .ok()
+                .and_then(|bytes| secp256k1::PublicKey::from_slice(&bytes).ok())
+                .is_some();
+            if !valid {
+                return Err("public_key must be a hex-encoded secp256k1 public key".to_string());
+            }
+        }
+        self.issuers.insert(issuer.address.to_lowercase(), issuer);
+        Ok(())
+    }
+
+    /// Returns `false` if the address was not registered.
+    pub fn remove(&mut self, address: &str) -> bool {
+        self.issuers.remove(&address.to_lowercase()).is_some()
+    }
+
+    pub fn get(&self, address: &str) -> Option<&TrustedIssuer> {
+        self.issuers.get(&address.to_lowercase())
+    }
+
+    pub fn list(&self) -> Vec<&TrustedIssuer> {
+        let mut issuers: Vec<&TrustedIssuer> = self.issuers.values().collect();
+        issuers.sort_by(|a, b| a.address.cmp(&b.address));
+        issuers
+    }
+}
@@ -0,0 +1,111 @@
+// src/contract/merkle.rs
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One step of a Chainpoint-style inclusion proof: the sibling hash and which
+/// side it sits on. Serializes as `{"left": "<hex>"}` or `{"right": "<hex>"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofStep {
+    Left(String),
+    Right(String),
+}
+
+pub fn hash_leaf(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Builds every level of the tree, leaves first. An odd node at the end of a
+/// level is promoted unchanged rather than paired with itself.
+fn levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves.to_vec()];
+    while levels.last().is_some_and(|level| level.len() > 1) {
+        let next = levels
+            .last()
+            .map(|level| {
+                level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => hash_pair(left, right),
+                        [single] => *single,
+                        _ => unreachable!(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Returns `None` for an empty leaf set.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> Option<[u8; 32]> {
+    levels(leaves).last()?.first().copied()
+}
+
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<ProofStep>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut proof = Vec::new();
+    let mut position = index;
+    for level in levels(leaves).iter().filter(|level| level.len() > 1) {
+        let sibling = position ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            proof.push(if sibling < position {
+                ProofStep::Left(hex::encode(hash))
+            } else {
+                ProofStep::Right(hex::encode(hash))
+            });
+        }
+        position /= 2;
+    }
+    Some(proof)
+}
+
+/// Recomputes the root from a leaf and its proof and compares it with `root_hex`.
+pub fn verify_proof(leaf: &[u8; 32], proof: &[ProofStep], root_hex: &str) -> bool {
+    let mut current = *leaf;
+    for step in proof {
+        let (ProofStep::Left(sibling_hex) | ProofStep::Right(sibling_hex)) = step;
+        let Some(sibling) = hex::decode(sibling_hex)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        else {
+            return false;
+        };
+        current = match step {
+            ProofStep::Left(_) => hash_pair(&sibling, &current),
+            ProofStep::Right(_) => hash_pair(&current, &sibling),
+        };
+    }
+    hex::encode(current).eq_ignore_ascii_case(root_hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_verify_against_root_for_every_leaf() {
+        for count in 1..=7 {
+            let leaves: Vec<[u8; 32]> = (0..count)
+                .map(|i| hash_leaf(format!("cert-{}", i).as_bytes()))
+                .collect();
+            let root = hex::encode(merkle_root(&leaves).unwrap());
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, index).unwrap();
+                assert!(verify_proof(leaf, &proof, &root));
+                assert!(!verify_proof(&hash_leaf(b"forged"), &proof, &root));
+            }
+        }
+        assert!(merkle_root(&[]).is_none());
+    }
+}
@@ -1 +1,7 @@
+#[allow(clippy::module_inception)]
 pub mod contract;
+pub mod credential;
+pub mod error;
+pub mod issuers;
+pub mod merkle;
+pub mod runtime;
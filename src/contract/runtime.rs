@@ -0,0 +1,944 @@
+// src/contract/runtime.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use wasmi::{
+    core::{HostError, TrapCode},
+    Caller, Config, EnforcedLimits, Engine, Extern, Linker, Memory, Module, StoreLimits,
+    StoreLimitsBuilder, TypedResumableCall, Val,
+};
+
+use super::contract::{Certificate, CertificateStatus, ContractExecutor};
+use super::error::ContractError;
+use crate::events::EventLog;
+use crate::token::{error::TokenError, fungible::Token};
+
+/// Account that collects gas fees.
+pub const GAS_FEE_ACCOUNT: &str = "__gas_fees__";
+/// Gas units bought by one native token; fees are rounded up to whole tokens.
+pub const GAS_PER_TOKEN: u64 = 10_000;
+pub const MAX_GAS_LIMIT: u64 = 100_000_000;
+pub const MAX_CODE_SIZE: usize = 512 * 1024;
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+/// Largest key, value or argument a host function copies out of contract memory.
+const MAX_HOST_BUFFER: usize = 64 * 1024;
+
+const DEPLOY_GAS_PER_BYTE: u64 = 10;
+const CALL_BASE_GAS: u64 = 1_000;
+const HOST_CALL_GAS: u64 = 100;
+const STORAGE_GAS_PER_BYTE: u64 = 50;
+const TRANSFER_GAS: u64 = 1_000;
+const CERTIFICATE_ISSUE_GAS: u64 = 10_000;
+const EVENT_GAS_PER_BYTE: u64 = 10;
+
+const OUT_OF_GAS: &str = "out of gas";
+
+/// Exported function run once when a contract is deployed, if present.
+const INIT_FUNCTION: &str = "init";
+
+/// A deployed WASM contract and its key-value storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmContract {
+    pub address: String,
+    pub deployer: String,
+    #[serde(with = "hex_bytes")]
+    pub code: Vec<u8>,
+    /// Hex SHA-256 of `code`.
+    pub code_hash: String,
+    pub deployed_at: DateTime<Utc>,
+    /// Hex-encoded keys and values.
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
+}
+
+/// Who runs a contract function and what they pay for it.
+#[derive(Debug, Clone)]
+pub struct Invocation {
+    pub caller: String,
+    pub input: Vec<u8>,
+    /// Native tokens moved from the caller to the contract before it runs.
+    pub value: u64,
+    pub gas_limit: u64,
+}
+
+/// Result of running a contract. The fee is charged whether or not the run
+/// succeeds; every other effect is rolled back on failure.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionOutcome {
+    pub contract: String,
+    pub success: bool,
+    pub gas_used: u64,
+    pub fee: u64,
+    /// Hex-encoded data the contract returned with `output_write`.
+    pub output: String,
+    pub error: Option<String>,
+}
+
+/// Deterministic address for a contract deployed by `deployer` in `transaction_id`.
+pub fn contract_address(deployer: &str, transaction_id: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", deployer.to_lowercase(), transaction_id));
+    format!("0x{}", hex::encode(&digest[..20]))
+}
+
+fn gas_fee(gas: u64) -> u64 {
+    gas.div_ceil(GAS_PER_TOKEN)
+}
+
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config
+        .consume_fuel(true)
+        .floats(false)
+        .enforced_limits(EnforcedLimits::strict());
+    Engine::new(&config)
+}
+
+/// The WASM engine and the host functions linked into every contract,
+/// built once per executor.
+#[derive(Clone)]
+pub(super) struct Runtime {
+    engine: Engine,
+    linker: Linker<HostState>,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        let engine = engine();
+        let linker = linker(&engine).expect("host function names are unique");
+        Runtime { engine, linker }
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runtime").finish_non_exhaustive()
+    }
+}
+
+/// State a running contract sees through its host functions: its own
+/// storage and what the run has done so far. Nothing here touches the
+/// executor or the token until the run succeeds.
+struct HostState {
+    contract: String,
+    caller: String,
+    input: Vec<u8>,
+    value: u64,
+    output: Vec<u8>,
+    /// Working copy of the contract's storage, kept only on success.
+    storage: BTreeMap<String, String>,
+    /// Token transfers, applied in order once the run succeeds.
+    transfers: Vec<(String, String, u64)>,
+    /// Certificates issued by the contract, recorded on success.
+    issued: Vec<Certificate>,
+    /// Logs emitted by the run, kept only on success.
+    logs: Vec<EventLog>,
+    limits: StoreLimits,
+}
+
+impl HostState {
+    /// `balance` after the pending transfers touching `account`, or `None`
+    /// if they could not all be applied.
+    fn pending_balance(&self, account: &str, balance: u64) -> Option<u64> {
+        self.transfers
+            .iter()
+            .try_fold(balance, |balance, (from, to, amount)| {
+                let balance = if from == account {
+                    balance.checked_sub(*amount)?
+                } else {
+                    balance
+                };
+                if to == account {
+                    balance.checked_add(*amount)
+                } else {
+                    Some(balance)
+                }
+            })
+    }
+
+    /// Queues a transfer if it would succeed after the pending ones.
+    fn transfer(&mut self, token: &Token, from: &str, to: &str, amount: u64) -> bool {
+        let spendable = self.pending_balance(from, token.available_at(from, Utc::now()));
+        let received = self.pending_balance(to, token.balance_of(to));
+        let fits =
+            from == to || received.is_some_and(|balance| balance.checked_add(amount).is_some());
+        if !fits || spendable.is_none_or(|spendable| spendable < amount) {
+            return false;
+        }
+        self.transfers
+            .push((from.to_string(), to.to_string(), amount));
+        self.logs
+            .push(EventLog::transfer(&token.symbol, from, to, amount));
+        true
+    }
+}
+
+/// A host call that needs the executor or the token. The host function
+/// returns it as an error, which suspends the contract until `execute`
+/// answers it and resumes the call with the result.
+#[derive(Debug)]
+enum HostQuery {
+    Balance(String),
+    Transfer { to: String, amount: u64 },
+    CertificateStatus(String),
+    CertificateIssue(Box<Certificate>),
+}
+
+impl fmt::Display for HostQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unanswered host query: {:?}", self)
+    }
+}
+
+impl HostError for HostQuery {}
+
+/// Applies the transfers in order, undoing them all if one fails.
+fn apply_transfers(
+    token: &mut Token,
+    transfers: &[(String, String, u64)],
+) -> Result<(), TokenError> {
+    for (applied, (from, to, amount)) in transfers.iter().enumerate() {
+        if let Err(err) = token.transfer(from, to, *amount) {
+            for (from, to, amount) in transfers[..applied].iter().rev() {
+                let _ = token.transfer(to, from, *amount);
+            }
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+fn charge(caller: &mut Caller<'_, HostState>, gas: u64) -> Result<(), wasmi::Error> {
+    let remaining = caller
+        .get_fuel()
+        .map_err(|err| wasmi::Error::new(err.to_string()))?;
+    if remaining < gas {
+        let _ = caller.set_fuel(0);
+        return Err(TrapCode::OutOfFuel.into());
+    }
+    caller
+        .set_fuel(remaining - gas)
+        .map_err(|err| wasmi::Error::new(err.to_string()))
+}
+
+fn memory(caller: &Caller<'_, HostState>) -> Result<Memory, wasmi::Error> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmi::Error::new("contract does not export its memory"))
+}
+
+fn read_bytes(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Result<Vec<u8>, wasmi::Error> {
+    let len = len as u32 as usize;
+    if len > MAX_HOST_BUFFER {
+        return Err(wasmi::Error::new("host buffer too large"));
+    }
+    let mut buffer = vec![0; len];
+    memory(caller)?
+        .read(caller, ptr as u32 as usize, &mut buffer)
+        .map_err(|err| wasmi::Error::new(err.to_string()))?;
+    Ok(buffer)
+}
+
+fn read_string(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Result<String, wasmi::Error> {
+    String::from_utf8(read_bytes(caller, ptr, len)?)
+        .map_err(|_| wasmi::Error::new("host string is not UTF-8"))
+}
+
+/// Writes as much of `bytes` as fits in `cap` and returns the full length.
+fn write_bytes(
+    caller: &mut Caller<'_, HostState>,
+    ptr: i32,
+    cap: i32,
+    bytes: &[u8],
+) -> Result<i32, wasmi::Error> {
+    let len = bytes.len().min(cap as u32 as usize);
+    memory(caller)?
+        .write(&mut *caller, ptr as u32 as usize, &bytes[..len])
+        .map_err(|err| wasmi::Error::new(err.to_string()))?;
+    Ok(bytes.len() as i32)
+}
+
+/// Amounts cross into WASM as `i64`; larger ones trap rather than wrap.
+fn to_i64(amount: u64) -> Result<i64, String> {
+    i64::try_from(amount).map_err(|_| format!("amount {} exceeds i64::MAX", amount))
+}
+
+fn certificate_status_code(cert: Option<&Certificate>) -> i32 {
+    match cert.map(|cert| cert.effective_status(Utc::now())) {
+        None => -1,
+        Some(CertificateStatus::Active) => 0,
+        Some(CertificateStatus::Suspended) => 1,
+        Some(CertificateStatus::Revoked) => 2,
+        Some(CertificateStatus::Expired) => 3,
+    }
+}
+
+/// Host functions imported from the `env` module. Byte strings are passed as
+/// (pointer, length) pairs into the contract's exported `memory`; functions
+/// that return variable-length data write up to `cap` bytes and return the
+/// full length. Functions that read node state outside the contract return
+/// a [`HostQuery`] for `execute` to answer.
+fn linker(engine: &Engine) -> Result<Linker<HostState>, wasmi::Error> {
+    let mut linker = Linker::new(engine);
+    linker
+        .func_wrap("env", "input_len", |caller: Caller<'_, HostState>| {
+            caller.data().input.len() as i32
+        })?
+        .func_wrap(
+            "env",
+            "input_read",
+            |mut caller: Caller<'_, HostState>, ptr: i32| -> Result<(), wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let input = caller.data().input.clone();
+                write_bytes(&mut caller, ptr, input.len() as i32, &input)?;
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "output_write",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<(), wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                caller.data_mut().output = read_bytes(&caller, ptr, len)?;
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "caller_read",
+            |mut caller: Caller<'_, HostState>, ptr: i32, cap: i32| {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let address = caller.data().caller.clone();
+                write_bytes(&mut caller, ptr, cap, address.as_bytes())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "value",
+            |caller: Caller<'_, HostState>| -> Result<i64, wasmi::Error> {
+                to_i64(caller.data().value).map_err(wasmi::Error::new)
+            },
+        )?
+        .func_wrap(
+            "env",
+            "emit_event",
+            |mut caller: Caller<'_, HostState>,
+             topic_ptr: i32,
+             topic_len: i32,
+             ptr: i32,
+             len: i32|
+             -> Result<(), wasmi::Error> {
+                let topic = read_string(&caller, topic_ptr, topic_len)?;
+                let data = read_bytes(&caller, ptr, len)?;
+                charge(
+                    &mut caller,
+                    HOST_CALL_GAS + EVENT_GAS_PER_BYTE * (topic.len() + data.len()) as u64,
+                )?;
+                let host = caller.data_mut();
+                let log = EventLog::new(&host.contract, &topic).with("data", hex::encode(data));
+                host.logs.push(log);
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "storage_read",
+            |mut caller: Caller<'_, HostState>,
+             key_ptr: i32,
+             key_len: i32,
+             ptr: i32,
+             cap: i32|
+             -> Result<i32, wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let key = hex::encode(read_bytes(&caller, key_ptr, key_len)?);
+                let Some(value) = caller.data().storage.get(&key) else {
+                    return Ok(-1);
+                };
+                let value = hex::decode(value).unwrap_or_default();
+                write_bytes(&mut caller, ptr, cap, &value)
+            },
+        )?
+        .func_wrap(
+            "env",
+            "storage_write",
+            |mut caller: Caller<'_, HostState>,
+             key_ptr: i32,
+             key_len: i32,
+             ptr: i32,
+             len: i32|
+             -> Result<(), wasmi::Error> {
+                let key = read_bytes(&caller, key_ptr, key_len)?;
+                let value = read_bytes(&caller, ptr, len)?;
+                charge(
+                    &mut caller,
+                    HOST_CALL_GAS + STORAGE_GAS_PER_BYTE * (key.len() + value.len()) as u64,
+                )?;
+                caller
+                    .data_mut()
+                    .storage
+                    .insert(hex::encode(key), hex::encode(value));
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "storage_remove",
+            |mut caller: Caller<'_, HostState>,
+             key_ptr: i32,
+             key_len: i32|
+             -> Result<(), wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let key = hex::encode(read_bytes(&caller, key_ptr, key_len)?);
+                caller.data_mut().storage.remove(&key);
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "balance",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i64, wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let account = read_string(&caller, ptr, len)?;
+                Err(wasmi::Error::host(HostQuery::Balance(account)))
+            },
+        )?
+        .func_wrap(
+            "env",
+            "transfer",
+            |mut caller: Caller<'_, HostState>,
+             ptr: i32,
+             len: i32,
+             amount: i64|
+             -> Result<i32, wasmi::Error> {
+                charge(&mut caller, TRANSFER_GAS)?;
+                let to = read_string(&caller, ptr, len)?;
+                let amount = u64::try_from(amount)
+                    .map_err(|_| wasmi::Error::new("transfer amount is negative"))?;
+                Err(wasmi::Error::host(HostQuery::Transfer { to, amount }))
+            },
+        )?
+        .func_wrap(
+            "env",
+            "certificate_status",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i32, wasmi::Error> {
+                charge(&mut caller, HOST_CALL_GAS)?;
+                let id = read_string(&caller, ptr, len)?;
+                match caller.data().issued.iter().find(|cert| cert.id == id) {
+                    Some(cert) => Ok(certificate_status_code(Some(cert))),
+                    None => Err(wasmi::Error::host(HostQuery::CertificateStatus(id))),
+                }
+            },
+        )?
+        .func_wrap(
+            "env",
+            "certificate_issue",
+            |mut caller: Caller<'_, HostState>,
+             id_ptr: i32,
+             id_len: i32,
+             holder_ptr: i32,
+             holder_len: i32,
+             description_ptr: i32,
+             description_len: i32|
+             -> Result<i32, wasmi::Error> {
+                charge(&mut caller, CERTIFICATE_ISSUE_GAS)?;
+                let id = read_string(&caller, id_ptr, id_len)?;
+                let holder = read_string(&caller, holder_ptr, holder_len)?;
+                let description = read_string(&caller, description_ptr, description_len)?;
+                let host = caller.data();
+                let cert = Certificate::new(&id, &holder, &description, &host.contract);
+                if host.issued.iter().any(|issued| issued.id == id) || cert.validate().is_err() {
+                    return Ok(1);
+                }
+                Err(wasmi::Error::host(HostQuery::CertificateIssue(Box::new(
+                    cert,
+                ))))
+            },
+        )?;
+    Ok(linker)
+}
+
+impl ContractExecutor {
+    /// Deploys a WASM contract and runs its `init` export, if any, with the
+    /// invocation's input. Errors mean nothing ran and no fee was charged.
+    pub fn deploy_contract(
+        &mut self,
+        token: &mut Token,
+        code: Vec<u8>,
+        invocation: &Invocation,
+        transaction_id: &str,
+    ) -> Result<ExecutionOutcome, ContractError> {
+        if code.len() > MAX_CODE_SIZE {
+            return Err(ContractError::CodeTooLarge {
+                max_bytes: MAX_CODE_SIZE,
+            });
+        }
+        let module = Module::new(&self.runtime.engine, &code)
+            .map_err(|err| ContractError::InvalidWasm(err.to_string()))?;
+        let address = contract_address(&invocation.caller, transaction_id);
+        if self.wasm_contracts.contains_key(&address) {
+            return Err(ContractError::AlreadyDeployed(address));
+        }
+        let intrinsic_gas = DEPLOY_GAS_PER_BYTE * code.len() as u64;
+        let init = module
+            .get_export(INIT_FUNCTION)
+            .is_some()
+            .then_some(INIT_FUNCTION);
+
+        let (outcome, storage) = self.execute(
+            token,
+            &module,
+            &address,
+            BTreeMap::new(),
+            init,
+            invocation,
+            intrinsic_gas,
+        )?;
+        if let Some(storage) = storage {
+            self.logs.push(
+                EventLog::new(&address, "ContractDeployed")
+                    .with("deployer", &invocation.caller)
+                    .with("code_hash", hex::encode(Sha256::digest(&code))),
+            );
+            self.wasm_contracts.insert(
+                address.clone(),
+                WasmContract {
+                    address,
+                    deployer: invocation.caller.clone(),
+                    code_hash: hex::encode(Sha256::digest(&code)),
+                    code,
+                    deployed_at: Utc::now(),
+                    storage,
+                },
+            );
+        }
+        Ok(outcome)
+    }
+
+    /// Calls an exported `() -> ()` function of a deployed contract. Errors
+    /// mean nothing ran and no fee was charged.
+    pub fn call_contract(
+        &mut self,
+        token: &mut Token,
+        address: &str,
+        function: &str,
+        invocation: &Invocation,
+    ) -> Result<ExecutionOutcome, ContractError> {
+        let contract = self
+            .wasm_contracts
+            .get(address)
+            .ok_or_else(|| ContractError::NotFound(address.to_string()))?;
+        let module = Module::new(&self.runtime.engine, &contract.code)
+            .map_err(|err| ContractError::InvalidWasm(err.to_string()))?;
+        if function == INIT_FUNCTION || module.get_export(function).is_none() {
+            return Err(ContractError::UnknownFunction {
+                address: address.to_string(),
+                function: function.to_string(),
+            });
+        }
+        let storage = contract.storage.clone();
+
+        let (outcome, storage) = self.execute(
+            token,
+            &module,
+            address,
+            storage,
+            Some(function),
+            invocation,
+            CALL_BASE_GAS,
+        )?;
+        if let (Some(storage), Some(contract)) = (storage, self.wasm_contracts.get_mut(address)) {
+            contract.storage = storage;
+        }
+        Ok(outcome)
+    }
+
+    /// Runs `function` against `storage` and settles the gas fee. Returns the
+    /// updated storage when the run succeeded.
+    #[allow(clippy::too_many_arguments)]
+    fn execute(
+        &mut self,
+        token: &mut Token,
+        module: &Module,
+        address: &str,
+        storage: BTreeMap<String, String>,
+        function: Option<&str>,
+        invocation: &Invocation,
+        intrinsic_gas: u64,
+    ) -> Result<(ExecutionOutcome, Option<BTreeMap<String, String>>), ContractError> {
+        if invocation.gas_limit > MAX_GAS_LIMIT {
+            return Err(ContractError::GasLimitTooHigh { max: MAX_GAS_LIMIT });
+        }
+        if invocation.gas_limit < intrinsic_gas {
+            return Err(ContractError::GasLimitTooLow { min: intrinsic_gas });
+        }
+        let max_fee = gas_fee(invocation.gas_limit);
+        if token.balance_of(&invocation.caller) < invocation.value.saturating_add(max_fee) {
+            return Err(ContractError::InsufficientFunds {
+                caller: invocation.caller.clone(),
+                max_fee,
+            });
+        }
+
+        let host = HostState {
+            contract: address.to_string(),
+            caller: invocation.caller.clone(),
+            input: invocation.input.clone(),
+            value: invocation.value,
+            output: Vec::new(),
+            storage,
+            transfers: Vec::new(),
+            issued: Vec::new(),
+            logs: Vec::new(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(MAX_MEMORY_BYTES)
+                .instances(1)
+                .build(),
+        };
+        let mut store = wasmi::Store::new(module.engine(), host);
+        store.limiter(|host| &mut host.limits);
+        let available = invocation.gas_limit - intrinsic_gas;
+        store.set_fuel(available).expect("fuel metering is enabled");
+
+        let caller = invocation.caller.clone();
+        let funded = invocation.value == 0
+            || store
+                .data_mut()
+                .transfer(token, &caller, address, invocation.value);
+        let mut result = if funded {
+            self.run(token, &mut store, module, function)
+        } else {
+            Err(format!("{} cannot cover the value", caller))
+        };
+        // Fuel left over after running out is too little to use, so charge it all
+        let gas_used = match &result {
+            Err(err) if err == OUT_OF_GAS => invocation.gas_limit,
+            _ => invocation.gas_limit - store.get_fuel().unwrap_or(0),
+        };
+
+        let mut host = store.into_data();
+        if result.is_ok() {
+            if let Err(err) = apply_transfers(token, &host.transfers) {
+                result = Err(err.to_string());
+            }
+        }
+        let storage = match &result {
+            Ok(()) => {
+                self.logs.append(&mut host.logs);
+                for cert in host.issued.drain(..) {
+                    let _ = self.issue_certificate(cert);
+                }
+                Some(std::mem::take(&mut host.storage))
+            }
+            Err(_) => None,
+        };
+
+        let fee = gas_fee(gas_used);
+        if token
+            .transfer(&invocation.caller, GAS_FEE_ACCOUNT, fee)
+            .is_ok()
+        {
+            self.logs.push(EventLog::transfer(
+                &token.symbol,
+                &invocation.caller,
+                GAS_FEE_ACCOUNT,
+                fee,
+            ));
+        }
+        Ok((
+            ExecutionOutcome {
+                contract: address.to_string(),
+                success: result.is_ok(),
+                gas_used,
+                fee,
+                output: hex::encode(&host.output),
+                error: result.err(),
+            },
+            storage,
+        ))
+    }
+
+    /// Instantiates the module and calls `function`, answering the host
+    /// queries it makes along the way.
+    fn run(
+        &self,
+        token: &Token,
+        store: &mut wasmi::Store<HostState>,
+        module: &Module,
+        function: Option<&str>,
+    ) -> Result<(), String> {
+        let describe = |err: &wasmi::Error| match err.as_trap_code() {
+            Some(TrapCode::OutOfFuel) => OUT_OF_GAS.to_string(),
+            _ => err.to_string(),
+        };
+        let instance = self
+            .runtime
+            .linker
+            .instantiate(&mut *store, module)
+            .and_then(|pre| pre.start(&mut *store))
+            .map_err(|err| describe(&err))?;
+        let Some(function) = function else {
+            return Ok(());
+        };
+        let mut call = instance
+            .get_typed_func::<(), ()>(&*store, function)
+            .and_then(|func| func.call_resumable(&mut *store, ()))
+            .map_err(|err| describe(&err))?;
+        while let TypedResumableCall::Resumable(invocation) = call {
+            let Some(query) = invocation.host_error().downcast_ref::<HostQuery>() else {
+                return Err(describe(invocation.host_error()));
+            };
+            let answer = self.answer(token, store.data_mut(), query)?;
+            call = invocation
+                .resume(&mut *store, &[answer])
+                .map_err(|err| describe(&err))?;
+        }
+        Ok(())
+    }
+
+    /// Answers a [`HostQuery`] from the executor and the token, as they
+    /// would be after the run's pending transfers.
+    fn answer(
+        &self,
+        token: &Token,
+        host: &mut HostState,
+        query: &HostQuery,
+    ) -> Result<Val, String> {
+        Ok(match query {
+            HostQuery::Balance(account) => {
+                let balance = host
+                    .pending_balance(account, token.balance_of(account))
+                    .unwrap_or_default();
+                Val::I64(to_i64(balance)?)
+            }
+            HostQuery::Transfer { to, amount } => {
+                let from = host.contract.clone();
+                Val::I32(if host.transfer(token, &from, to, *amount) {
+                    0
+                } else {
+                    1
+                })
+            }
+            HostQuery::CertificateStatus(id) => {
+                Val::I32(certificate_status_code(self.certificates.get(id)))
+            }
+            HostQuery::CertificateIssue(cert) => {
+                if self.certificates.contains_key(&cert.id) {
+                    return Ok(Val::I32(1));
+                }
+                host.issued.push(cert.as_ref().clone());
+                Val::I32(0)
+            }
+        })
+    }
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        hex::decode(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTER: &str = r#"
+        (module
+          (import "env" "storage_read" (func $read (param i32 i32 i32 i32) (result i32)))
+          (import "env" "storage_write" (func $write (param i32 i32 i32 i32)))
+          (import "env" "output_write" (func $output (param i32 i32)))
+          (import "env" "transfer" (func $transfer (param i32 i32 i64) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "count")
+          (data (i32.const 16) "bob")
+          (func (export "increment")
+            (if (i32.lt_s (call $read (i32.const 0) (i32.const 5) (i32.const 32) (i32.const 4)) (i32.const 0))
+              (then (i32.store (i32.const 32) (i32.const 0))))
+            (i32.store (i32.const 32) (i32.add (i32.load (i32.const 32)) (i32.const 1)))
+            (call $write (i32.const 0) (i32.const 5) (i32.const 32) (i32.const 4))
+            (call $output (i32.const 32) (i32.const 4)))
+          (func (export "pay_bob")
+            (drop (call $transfer (i32.const 16) (i32.const 3) (i64.const 5)))
+            unreachable)
+          (func (export "spin") (loop $l (br $l))))
+    "#;
+
+    const PAYER: &str = r#"
+        (module
+          (import "env" "transfer" (func $transfer (param i32 i32 i64) (result i32)))
+          (import "env" "balance" (func $balance (param i32 i32) (result i64)))
+          (import "env" "certificate_issue"
+            (func $issue (param i32 i32 i32 i32 i32 i32) (result i32)))
+          (import "env" "certificate_status" (func $status (param i32 i32) (result i32)))
+          (import "env" "output_write" (func $output (param i32 i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "bob")
+          (data (i32.const 16) "CERT-1")
+          (data (i32.const 32) "A course")
+          (func (export "pay_and_issue")
+            (i32.store (i32.const 64) (call $transfer (i32.const 0) (i32.const 3) (i64.const 4)))
+            (i64.store (i32.const 68) (call $balance (i32.const 0) (i32.const 3)))
+            (i32.store (i32.const 76)
+              (call $issue (i32.const 16) (i32.const 6) (i32.const 0) (i32.const 3)
+                (i32.const 32) (i32.const 8)))
+            (i32.store (i32.const 80) (call $status (i32.const 16) (i32.const 6)))
+            (call $output (i32.const 64) (i32.const 20))))
+    "#;
+
+    fn invocation(gas_limit: u64) -> Invocation {
+        Invocation {
+            caller: "alice".to_string(),
+            input: Vec::new(),
+            value: 0,
+            gas_limit,
+        }
+    }
+
+    #[test]
+    fn runs_contracts_with_storage_and_gas() {
+        let mut contracts = ContractExecutor::new();
+        let mut token = Token::new("Hikma", "HKM", 1_000, "alice");
+        let code = wat::parse_str(COUNTER).unwrap();
+        let deployed = contracts
+            .deploy_contract(&mut token, code, &invocation(1_000_000), "tx-1")
+            .unwrap();
+        assert!(deployed.success);
+        let address = deployed.contract;
+
+        for expected in 1..=2u32 {
+            let outcome = contracts
+                .call_contract(&mut token, &address, "increment", &invocation(100_000))
+                .unwrap();
+            assert!(outcome.success);
+            assert_eq!(outcome.output, hex::encode(expected.to_le_bytes()));
+            assert!(outcome.gas_used > CALL_BASE_GAS && outcome.fee >= 1);
+        }
+        assert_eq!(contracts.wasm_contracts[&address].storage.len(), 1);
+        let topics: Vec<String> = contracts
+            .take_logs()
+            .into_iter()
+            .map(|log| log.topic)
+            .collect();
+        assert_eq!(topics.first().map(String::as_str), Some("Transfer"));
+        assert!(topics.contains(&"ContractDeployed".to_string()));
+        assert!(token.balance_of(GAS_FEE_ACCOUNT) > 0);
+        assert_eq!(
+            token.balance_of("alice") + token.balance_of(GAS_FEE_ACCOUNT),
+            1_000
+        );
+    }
+
+    #[test]
+    fn host_queries_see_pending_effects() {
+        let mut contracts = ContractExecutor::new();
+        let mut token = Token::new("Hikma", "HKM", 1_000, "alice");
+        let code = wat::parse_str(PAYER).unwrap();
+        let address = contracts
+            .deploy_contract(&mut token, code, &invocation(1_000_000), "tx-1")
+            .unwrap()
+            .contract;
+
+        let mut paying = invocation(100_000);
+        paying.value = 10;
+        let outcome = contracts
+            .call_contract(&mut token, &address, "pay_and_issue", &paying)
+            .unwrap();
+        assert!(outcome.success, "{:?}", outcome.error);
+        // Transfer succeeded, bob's balance includes it, and the certificate
+        // issued earlier in the run is already active
+        let mut expected = 0i32.to_le_bytes().to_vec();
+        expected.extend(4i64.to_le_bytes());
+        expected.extend(0i32.to_le_bytes());
+        expected.extend(0i32.to_le_bytes());
+        assert_eq!(outcome.output, hex::encode(expected));
+        assert_eq!(token.balance_of("bob"), 4);
+        assert_eq!(token.balance_of(&address), 6);
+        assert_eq!(contracts.certificates["CERT-1"].issuer, address);
+    }
+
+    #[test]
+    fn amounts_beyond_i64_trap() {
+        const READER: &str = r#"
+            (module
+              (import "env" "balance" (func $balance (param i32 i32) (result i64)))
+              (import "env" "value" (func $value (result i64)))
+              (memory (export "memory") 1)
+              (data (i32.const 0) "alice")
+              (func (export "balance") (drop (call $balance (i32.const 0) (i32.const 5))))
+              (func (export "value") (drop (call $value))))
+        "#;
+        let mut contracts = ContractExecutor::new();
+        let mut token = Token::new("Hikma", "HKM", u64::MAX, "alice");
+        let code = wat::parse_str(READER).unwrap();
+        let address = contracts
+            .deploy_contract(&mut token, code, &invocation(1_000_000), "tx-1")
+            .unwrap()
+            .contract;
+
+        let outcome = contracts
+            .call_contract(&mut token, &address, "balance", &invocation(100_000))
+            .unwrap();
+        assert!(!outcome.success);
+        assert!(outcome.error.unwrap().contains("exceeds i64::MAX"));
+
+        let mut paying = invocation(100_000);
+        paying.value = i64::MAX as u64 + 1;
+        let outcome = contracts
+            .call_contract(&mut token, &address, "value", &paying)
+            .unwrap();
+        assert!(!outcome.success);
+        assert!(outcome.error.unwrap().contains("exceeds i64::MAX"));
+        assert_eq!(token.balance_of(&address), 0);
+    }
+
+    #[test]
+    fn failed_calls_roll_back_but_pay_gas() {
+        let mut contracts = ContractExecutor::new();
+        let mut token = Token::new("Hikma", "HKM", 1_000, "alice");
+        let code = wat::parse_str(COUNTER).unwrap();
+        let address = contracts
+            .deploy_contract(&mut token, code, &invocation(1_000_000), "tx-1")
+            .unwrap()
+            .contract;
+        let before = token.balance_of("alice");
+
+        let mut paying = invocation(100_000);
+        paying.value = 10;
+        let outcome = contracts
+            .call_contract(&mut token, &address, "pay_bob", &paying)
+            .unwrap();
+        assert!(!outcome.success);
+        assert_eq!(token.balance_of("bob"), 0);
+        assert_eq!(token.balance_of(&address), 0);
+        assert_eq!(token.balance_of("alice"), before - outcome.fee);
+        contracts.take_logs();
+
+        let outcome = contracts
+            .call_contract(&mut token, &address, "spin", &invocation(50_000))
+            .unwrap();
+        assert_eq!(outcome.error.as_deref(), Some("out of gas"));
+        assert_eq!(outcome.gas_used, 50_000);
+        assert_eq!(outcome.fee, 5);
+        // Only the gas fee transfer survives a failed run
+        assert_eq!(
+            contracts.take_logs(),
+            vec![EventLog::transfer("HKM", "alice", GAS_FEE_ACCOUNT, 5)]
+        );
+
+        assert!(contracts
+            .call_contract(&mut token, &address, "missing", &invocation(50_000))
+            .is_err());
+        assert!(contracts
+            .call_contract(&mut token, &address, "spin", &invocation(u64::MAX))
+            .is_err());
+    }
+}
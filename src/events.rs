@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::sync::broadcast;
+
+use crate::blockchain::transaction::Transaction;
+
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Emitter name of logs from the built-in certificate contract.
+pub const CERTIFICATE_CONTRACT: &str = "certificates";
+/// Emitter name of token registry logs; the `token` attribute names the token.
+pub const TOKEN_CONTRACT: &str = "token";
+/// Emitter name of NFT logs.
+pub const NFT_CONTRACT: &str = "nft";
+/// Attributes holding account addresses, used for per-account subscriptions.
+const ACCOUNT_ATTRIBUTES: [&str; 9] = [
+    "holder", "issuer", "from", "to", "account", "deployer", "owner", "spender", "minter",
+];
+
+/// A structured log emitted by a contract operation or token transfer. Logs
+/// are kept in their transaction's receipt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventLog {
+    /// [`CERTIFICATE_CONTRACT`], [`TOKEN_CONTRACT`], [`NFT_CONTRACT`] or a
+    /// WASM contract address.
+    pub contract: String,
+    /// Event name, e.g. `CertificateIssued` or `Transfer`.
+    pub topic: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl EventLog {
+    pub fn new(contract: &str, topic: &str) -> Self {
+        EventLog {
+            contract: contract.to_string(),
+            topic: topic.to_string(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn transfer(token: &str, from: &str, to: &str, amount: u64) -> Self {
+        EventLog::new(TOKEN_CONTRACT, "Transfer")
+            .with("token", token)
+            .with("from", from)
+            .with("to", to)
+            .with("amount", amount)
+    }
+
+    /// An allowance change; `amount` is the new allowance.
+    pub fn approval(token: &str, owner: &str, spender: &str, amount: u64) -> Self {
+        EventLog::new(TOKEN_CONTRACT, "Approval")
+            .with("token", token)
+            .with("owner", owner)
+            .with("spender", spender)
+            .with("amount", amount)
+    }
+
+    pub fn accounts(&self) -> Vec<&str> {
+        ACCOUNT_ATTRIBUTES
+            .iter()
+            .filter_map(|key| self.attributes.get(*key))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Case-insensitive attribute match; a missing attribute never matches.
+    pub fn has_attribute(&self, key: &str, value: &str) -> bool {
+        self.attributes
+            .get(key)
+            .is_some_and(|actual| actual.eq_ignore_ascii_case(value))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum ChainEvent {
+    NewBlock {
+        index: u64,
+        hash: String,
+        validator: Option<String>,
+        transaction_count: usize,
+    },
+    PendingTransaction(Transaction),
+    FinalityAdvanced {
+        finalized_height: u64,
+    },
+    Slashed {
+        address: String,
+        amount: u64,
+        reason: String,
+    },
+    CertificateVerified {
+        id: String,
+        issued_to: String,
+    },
+    /// `issued_to` is `None` when the certificate does not exist.
+    CertificateVerificationFailed {
+        id: String,
+        issued_to: Option<String>,
+        reason: String,
+    },
+    RewardPolicyUpdated {
+        issuer: String,
+        credential_type: String,
+        amount: u64,
+        budget: u64,
+    },
+    /// A log from a pending transaction's receipt.
+    Log {
+        transaction_id: String,
+        log: EventLog,
+    },
+}
+
+impl ChainEvent {
+    /// The event name; logs are named after their topic.
+    pub fn event_type(&self) -> &str {
+        match self {
+            ChainEvent::NewBlock { .. } => "NewBlock",
+            ChainEvent::PendingTransaction(_) => "PendingTransaction",
+            ChainEvent::FinalityAdvanced { .. } => "FinalityAdvanced",
+            ChainEvent::Slashed { .. } => "Slashed",
+            ChainEvent::CertificateVerified { .. } => "CertificateVerified",
+            ChainEvent::CertificateVerificationFailed { .. } => "CertificateVerificationFailed",
+            ChainEvent::RewardPolicyUpdated { .. } => "RewardPolicyUpdated",
+            ChainEvent::Log { log, .. } => &log.topic,
+        }
+    }
+
+    /// Accounts an event is about, used for per-account subscriptions.
+    pub fn accounts(&self) -> Vec<&str> {
+        match self {
+            ChainEvent::NewBlock { validator, .. } => {
+                validator.iter().map(String::as_str).collect()
+            }
+            ChainEvent::PendingTransaction(tx) => {
+                let mut accounts = vec![tx.to.as_str()];
+                if let Some(from) = &tx.from {
+                    accounts.push(from.as_str());
+                }
+                accounts
+            }
+            ChainEvent::FinalityAdvanced { .. } => Vec::new(),
+            ChainEvent::Slashed { address, .. } => vec![address.as_str()],
+            ChainEvent::CertificateVerified { issued_to, .. } => vec![issued_to.as_str()],
+            ChainEvent::CertificateVerificationFailed { issued_to, .. } => {
+                issued_to.iter().map(String::as_str).collect()
+            }
+            ChainEvent::RewardPolicyUpdated { issuer, .. } => vec![issuer.as_str()],
+            ChainEvent::Log { log, .. } => log.accounts(),
+        }
+    }
+}
+
+/// Fan-out channel for chain events. Publishing never blocks and is a no-op
+/// when nobody is subscribed.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ChainEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: ChainEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventFilter {
+    /// Comma-separated event types, e.g. `NewBlock,Slashed`.
+    pub types: Option<String>,
+    pub account: Option<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &ChainEvent) -> bool {
+        if let Some(types) = &self.types {
+            let event_type = event.event_type();
+            if !types
+                .split(',')
+                .map(str::trim)
+                .any(|value| value.eq_ignore_ascii_case(event_type))
+            {
+                return false;
+            }
+        }
+
+        if let Some(account) = &self.account {
+            if !event
+                .accounts()
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case(account))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_type_and_account() {
+        let event = ChainEvent::Log {
+            transaction_id: "tx-1".to_string(),
+            log: EventLog::new(CERTIFICATE_CONTRACT, "CertificateIssued")
+                .with("id", "CERT001")
+                .with("holder", "Alice")
+                .with("issuer", "0xuni"),
+        };
+
+        assert!(EventFilter::default().matches(&event));
+        let by_type = EventFilter {
+            types: Some("NewBlock, CertificateIssued".to_string()),
+            account: None,
+        };
+        assert!(by_type.matches(&event));
+        let other_account = EventFilter {
+            types: None,
+            account: Some("Bob".to_string()),
+        };
+        assert!(!other_account.matches(&event));
+        let finality = ChainEvent::FinalityAdvanced {
+            finalized_height: 3,
+        };
+        assert!(!other_account.matches(&finality));
+    }
+}
@@ -3,83 +3,154 @@ mod auth;
 mod blockchain;
 mod consensus;
 mod contract;
+mod events;
 mod governance;
 mod p2p;
 mod persistence;
 mod token;
 
-use api::routes::{api_routes, AppState};
-use auth::{routes::auth_routes, AuthManager};
+use api::{
+    routes::{api_routes, run_persister, AppState},
+    stream::stream_routes,
+};
+use auth::{rbac::Role, routes::auth_routes, siwe::SiweConfig, AuthManager};
 use axum::http::Method;
-use blockchain::{chain::Blockchain, transaction::Transaction};
-use consensus::pos::Staker;
-use contract::contract::ContractExecutor;
+use events::EventBus;
 use p2p::service::P2PService;
-use persistence::load_state;
+use persistence::{load_state, PersistSignal, STATE_PATH};
+use std::path::Path;
 use std::sync::Arc;
-use token::fungible::Token;
+use token::{fungible::Token, registry::TokenRegistry};
 use tokio::net::TcpListener;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, RwLock};
 use tower_http::cors::{Any, CorsLayer};
 
+fn env_duration_seconds(name: &str) -> Option<chrono::Duration> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|seconds| *seconds > 0)
+        .map(chrono::Duration::seconds)
+}
+
 #[tokio::main]
 async fn main() {
-    let difficulty = 2;
-
     // Initialize Blockchain, Token, Contracts, and Pending Transactions
-    let snapshot = load_state();
-    let chain = Arc::new(Mutex::new(
+    let snapshot = match load_state(Path::new(STATE_PATH)) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            eprintln!("❌ Cannot load {}: {}", STATE_PATH, err);
+            eprintln!("   Move it aside or run ./ops/reset_chain.sh to start a new chain.");
+            std::process::exit(1);
+        }
+    };
+    let chain = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.chain.clone())
-            .unwrap_or_else(|| Blockchain::new(difficulty)),
+            .unwrap_or_default(),
     ));
-    let token = Arc::new(Mutex::new(
+    let tokens = Arc::new(RwLock::new(
         snapshot
             .as_ref()
-            .map(|state| state.token.clone())
-            .unwrap_or_else(|| Token::new("Metacation Token", "MCT", 1000, "admin")),
+            .and_then(|state| state.tokens.clone())
+            .unwrap_or_else(|| {
+                TokenRegistry::new(Token::new("Metacation Token", "MCT", 1000, "admin"))
+            }),
     ));
-    let contracts = Arc::new(Mutex::new(
+    let contracts = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.contracts.clone())
-            .unwrap_or_else(ContractExecutor::new),
+            .unwrap_or_default(),
     ));
-    let pending_transactions = Arc::new(Mutex::new(
+    let pending_transactions = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.pending_transactions.clone())
-            .unwrap_or_else(|| Vec::<Transaction>::new()),
+            .unwrap_or_default(),
     ));
-    let auth_manager = Arc::new(Mutex::new(AuthManager::new()));
-    let stakers = Arc::new(Mutex::new(
+    let mut auth_manager = match std::env::var("AUTH_JWT_SECRET") {
+        Ok(secret) if !secret.is_empty() => AuthManager::new().with_jwt_secret(secret.as_bytes()),
+        _ => AuthManager::new(),
+    };
+    if let Some(ttl) = env_duration_seconds("SESSION_TTL_SECONDS") {
+        auth_manager = auth_manager.with_session_ttl(ttl);
+    }
+    if let Some(ttl) = env_duration_seconds("NONCE_TTL_SECONDS") {
+        auth_manager = auth_manager.with_nonce_ttl(ttl);
+    }
+    let siwe_defaults = SiweConfig::default();
+    auth_manager = auth_manager.with_siwe_config(SiweConfig {
+        domain: std::env::var("SIWE_DOMAIN").unwrap_or(siwe_defaults.domain),
+        uri: std::env::var("SIWE_URI").ok(),
+        chain_id: std::env::var("SIWE_CHAIN_ID")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(siwe_defaults.chain_id),
+    });
+    if let Some(state) = snapshot.as_ref() {
+        auth_manager = auth_manager.with_revoked_sessions(state.revoked_sessions.clone());
+    }
+    let auth_manager = Arc::new(RwLock::new(auth_manager));
+    let stakers = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.stakers.clone())
-            .unwrap_or_else(|| Vec::<Staker>::new()),
+            .unwrap_or_default(),
     ));
-    let peers = Arc::new(Mutex::new(
+    let peers = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.peers.clone())
-            .unwrap_or_else(Vec::new),
+            .unwrap_or_default(),
     ));
-    let governance = Arc::new(Mutex::new(
+    let governance = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.governance.clone())
             .unwrap_or_default(),
     ));
-    let slash_evidence = Arc::new(Mutex::new(
+    let slash_evidence = Arc::new(RwLock::new(
         snapshot
             .as_ref()
             .map(|state| state.slash_evidence.clone())
             .unwrap_or_default(),
     ));
-    let metrics = Arc::new(Mutex::new(api::routes::Metrics::default()));
+    let mut roles = snapshot
+        .as_ref()
+        .map(|state| state.roles.clone())
+        .unwrap_or_default();
+    // Bootstrap admins; further roles are granted through /rbac/grant
+    for address in std::env::var("ADMIN_ADDRESSES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+    {
+        roles.grant(address, Role::Admin);
+    }
+    let roles = Arc::new(RwLock::new(roles));
+    let issuers = Arc::new(RwLock::new(
+        snapshot
+            .as_ref()
+            .map(|state| state.issuers.clone())
+            .unwrap_or_default(),
+    ));
+    let receipts = Arc::new(RwLock::new(
+        snapshot
+            .as_ref()
+            .map(|state| state.receipts.clone())
+            .unwrap_or_default(),
+    ));
+    let nfts = Arc::new(RwLock::new(
+        snapshot
+            .as_ref()
+            .map(|state| state.nfts.clone())
+            .unwrap_or_default(),
+    ));
+    let metrics = Arc::new(RwLock::new(api::routes::Metrics::default()));
     let p2p_token = std::env::var("P2P_TOKEN").ok();
-    let admin_token = std::env::var("ADMIN_TOKEN").ok();
 
     let p2p_service = Arc::new(
         P2PService::new(
@@ -90,17 +161,17 @@ async fn main() {
     );
 
     let finality_depth = {
-        let governance = governance.lock().await;
+        let governance = governance.read().await;
         governance.finality_depth
     };
     {
-        let mut chain = chain.lock().await;
+        let mut chain = chain.write().await;
         chain.apply_finality(finality_depth);
     }
 
     let app_state = AppState {
         chain,
-        token,
+        tokens,
         contracts,
         pending_transactions,
         auth_manager,
@@ -108,12 +179,25 @@ async fn main() {
         peers,
         governance,
         slash_evidence,
+        roles,
+        issuers,
+        receipts,
+        nfts,
+        mining: Arc::default(),
         metrics,
+        events: EventBus::new(),
+        persist: PersistSignal::default(),
         p2p_token,
-        admin_token,
         p2p_service,
     };
 
+    let (stop_persister, persister_shutdown) = oneshot::channel();
+    let persister = tokio::spawn(run_persister(
+        app_state.clone(),
+        STATE_PATH.into(),
+        persister_shutdown,
+    ));
+
     // Configure CORS to allow React app on localhost:5173
     let cors = CorsLayer::new()
         .allow_origin(
@@ -132,8 +216,9 @@ async fn main() {
         .allow_credentials(false);
 
     // Combine API routes with auth routes
-    let app = api_routes()
+    let app = api_routes(app_state.clone())
         .merge(auth_routes())
+        .merge(stream_routes())
         .with_state(app_state)
         .layer(cors);
 
@@ -143,21 +228,55 @@ async fn main() {
     println!("  🔐 AUTHENTICATION:");
     println!("      🎫 POST /auth/nonce");
     println!("      ✅ POST /auth/verify");
+    println!("      🔄 POST /auth/refresh");
     println!("      🚪 DELETE /auth/logout");
     println!("  🎓 CERTIFICATES:");
     println!("      📜 POST /certificates/issue");
+    println!("      📦 POST /certificates/issue/batch");
+    println!("      🧾 GET  /certificates/{{id}}/receipt");
+    println!("      🌳 POST /certificates/batch/verify");
     println!("      ✅ POST /certificates/verify");
+    println!("      🎁 POST /certificates/rewards/claim");
+    println!("      ⏸️  POST /certificates/suspend");
+    println!("      ▶️  POST /certificates/reinstate");
+    println!("      🚫 POST /certificates/revoke");
+    println!("      📋 GET  /certificates/revocations");
+    println!("      ✍️  POST /certificates/signing-payload");
+    println!("      📤 GET  /certificates/{{id}}/credential");
+    println!("      🪪 POST /credentials/verify");
+    println!("  🏛️  TRUSTED ISSUERS:");
+    println!("      📋 GET  /issuers");
+    println!("      ➕ POST /issuers/register");
+    println!("      ➖ POST /issuers/remove");
     println!("  💰 TOKENS:");
-    println!("      💸 POST /tokens/transfer");
-    println!("      📊 GET  /tokens/balance/{{account}}");
+    println!("      🪙 GET  /tokens");
+    println!("      🪙 POST /tokens");
+    println!("      💸 POST /tokens/{{token}}/transfer");
+    println!("      🏭 POST /tokens/{{token}}/mint");
+    println!("      🔥 POST /tokens/{{token}}/burn");
+    println!("      👥 POST /tokens/{{token}}/minters");
+    println!("      🤝 POST /tokens/{{token}}/approve");
+    println!("      💸 POST /tokens/{{token}}/transfer-from");
+    println!("      🔎 GET  /tokens/{{token}}/allowance/{{owner}}/{{spender}}");
+    println!("      📊 GET  /tokens/{{token}}/balance/{{account}}");
+    println!("      ⏳ POST /tokens/{{token}}/vesting");
+    println!("      ⏳ GET  /tokens/{{token}}/vesting/{{account}}");
+    println!("      🏅 POST /nfts");
+    println!("      🏅 GET  /nfts/{{id}}");
+    println!("      🔁 POST /nfts/{{id}}/transfer");
+    println!("      🔥 POST /nfts/{{id}}/burn");
+    println!("      🏅 GET  /accounts/{{address}}/nfts");
     println!("  📦 BLOCKCHAIN:");
     println!("      📚 GET  /blocks");
     println!("      🔢 GET  /blocks/{{index}}");
+    println!("      🔑 GET  /blocks/hash/{{hash}}");
+    println!("      🧾 GET  /headers");
     println!("      📊 GET  /blockchain/stats");
     println!("  ⛏️  MINING:");
     println!("      ⚡ POST /mine");
     println!("      ⚙️  GET  /mining/difficulty");
-    println!("      ⚙️  POST /mining/difficulty");
+    println!("      📋 GET  /mining/jobs");
+    println!("      🔎 GET  /mining/jobs/{{id}}");
     println!("  🧮 STAKING:");
     println!("      ➕ POST /staking/deposit");
     println!("      ➖ POST /staking/withdraw");
@@ -166,12 +285,30 @@ async fn main() {
     println!("      🔍 GET  /blockchain/validate");
     println!("      🔎 GET  /blocks/{{index}}/validate");
     println!("      📋 GET  /validate (tutorial compat)");
+    println!("  🛡️  ACCESS CONTROL:");
+    println!("      ➕ POST /rbac/grant");
+    println!("      ➖ POST /rbac/revoke");
+    println!("      👤 GET  /rbac/roles/{{address}}");
     println!("  📄 TRANSACTIONS:");
     println!("      ⏳ GET  /transactions/pending");
-    println!("");
+    println!("  📡 EVENTS:");
+    println!("      🔔 GET  /events/stream (SSE)");
+    println!("      🔍 GET  /events");
+    println!("      🧾 GET  /receipts/{{transaction_id}}");
+    println!("      🧾 GET  /blocks/{{index}}/receipts");
+    println!();
     println!("🌟 Complete blockchain with wallet authentication & smart contracts!");
 
     let listener = TcpListener::bind("0.0.0.0:3000").await.unwrap();
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .unwrap();
+
+    // Write any changes made since the last save before exiting
+    let _ = stop_persister.send(());
+    let _ = persister.await;
 }
@@ -16,6 +16,7 @@ pub struct P2PEnvelope {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
+#[allow(clippy::large_enum_variant)]
 pub enum P2PPayload {
     Ping,
     PeerAnnounce { address: String },
@@ -1,21 +1,32 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
+use std::io::ErrorKind;
 use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Notify;
 
 use crate::{
-    blockchain::{chain::Blockchain, transaction::Transaction},
+    auth::rbac::RoleRegistry,
+    blockchain::{chain::Blockchain, receipt::ReceiptStore, transaction::Transaction},
     consensus::pos::Staker,
-    contract::contract::ContractExecutor,
+    contract::{contract::ContractExecutor, issuers::IssuerRegistry},
     governance::GovernanceConfig,
-    token::fungible::Token,
+    token::{fungible::Token, nft::NftRegistry, registry::TokenRegistry},
 };
 
-const STATE_PATH: &str = "data/state.json";
+pub const STATE_PATH: &str = "data/state.json";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppSnapshot {
     pub chain: Blockchain,
-    pub token: Token,
+    /// Native token of snapshots saved before the token registry existed.
+    #[serde(default, skip_serializing)]
+    pub token: Option<Token>,
+    #[serde(default)]
+    pub tokens: Option<TokenRegistry>,
     pub contracts: ContractExecutor,
     pub pending_transactions: Vec<Transaction>,
     pub stakers: Vec<Staker>,
@@ -25,6 +36,17 @@ pub struct AppSnapshot {
     pub governance: GovernanceConfig,
     #[serde(default)]
     pub slash_evidence: Vec<SlashEvidence>,
+    #[serde(default)]
+    pub roles: RoleRegistry,
+    #[serde(default)]
+    pub issuers: IssuerRegistry,
+    #[serde(default)]
+    pub receipts: ReceiptStore,
+    #[serde(default)]
+    pub nfts: NftRegistry,
+    /// Logged-out JWT session ids and when each token expires.
+    #[serde(default)]
+    pub revoked_sessions: HashMap<String, DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,16 +58,74 @@ pub struct SlashEvidence {
     pub slashed_amount: u64,
 }
 
-pub fn load_state() -> Option<AppSnapshot> {
-    let contents = fs::read_to_string(STATE_PATH).ok()?;
-    serde_json::from_str(&contents).ok()
+/// Why a saved state file could not be loaded. The node refuses to start
+/// rather than overwrite it with a fresh chain.
+#[derive(Debug)]
+pub enum LoadError {
+    Read(std::io::Error),
+    /// Not a snapshot this version can parse.
+    Parse(serde_json::Error),
+    /// The chain fails validation, e.g. blocks mined under an older block
+    /// hash or difficulty format.
+    InvalidChain,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Read(err) => write!(f, "Failed to read saved state: {}", err),
+            LoadError::Parse(err) => write!(f, "Saved state is not in a known format: {}", err),
+            LoadError::InvalidChain => write!(
+                f,
+                "Saved chain fails validation; it was probably written by an older version"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Loads the snapshot at `path`, or `None` if there is no saved state yet.
+pub fn load_state(path: &Path) -> Result<Option<AppSnapshot>, LoadError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(LoadError::Read(err)),
+    };
+    let mut snapshot: AppSnapshot = serde_json::from_str(&contents).map_err(LoadError::Parse)?;
+    if !snapshot.chain.is_valid() {
+        return Err(LoadError::InvalidChain);
+    }
+    snapshot.contracts.rebuild_indexes();
+    if snapshot.tokens.is_none() {
+        snapshot.tokens = snapshot.token.take().map(TokenRegistry::new);
+    }
+    Ok(Some(snapshot))
 }
 
-pub fn save_state(snapshot: &AppSnapshot) -> std::io::Result<()> {
-    if let Some(parent) = Path::new(STATE_PATH).parent() {
+/// Writes the snapshot to a temporary file next to `path` and renames it into
+/// place, so a crash mid-write leaves the previous snapshot intact.
+pub fn save_state(path: &Path, snapshot: &AppSnapshot) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    let data = serde_json::to_string_pretty(snapshot)
-        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-    fs::write(STATE_PATH, data)
+    let data = serde_json::to_string_pretty(snapshot).map_err(std::io::Error::other)?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Wakes the background state writer. Requests made while a write is in
+/// flight coalesce into a single follow-up write.
+#[derive(Debug, Clone, Default)]
+pub struct PersistSignal(Arc<Notify>);
+
+impl PersistSignal {
+    pub fn request(&self) {
+        self.0.notify_one();
+    }
+
+    pub async fn requested(&self) {
+        self.0.notified().await;
+    }
 }
@@ -0,0 +1,120 @@
+use std::fmt;
+
+/// Why a token operation was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    UnknownToken(String),
+    InvalidName,
+    InvalidSymbol {
+        max_len: usize,
+    },
+    SymbolTaken(String),
+    InsufficientBalance {
+        symbol: String,
+        account: String,
+    },
+    InsufficientAllowance {
+        symbol: String,
+        owner: String,
+        spender: String,
+        allowance: u64,
+    },
+    BalanceOverflow {
+        symbol: String,
+        account: String,
+    },
+    NotTransferable(String),
+    NotMintAuthority {
+        symbol: String,
+        account: String,
+    },
+    NotTokenOwner {
+        symbol: String,
+        owner: String,
+    },
+    NativeMintersGoverned,
+    MaxSupplyExceeded {
+        symbol: String,
+        amount: u64,
+    },
+    MaxSupplyBelowSupply {
+        symbol: String,
+        max_supply: u64,
+        total_supply: u64,
+    },
+    /// Burning `amount` would take the total supply below zero.
+    SupplyUnderflow {
+        symbol: String,
+        amount: u64,
+    },
+    InvalidVesting(String),
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenError::UnknownToken(id) => write!(f, "Unknown token: {}", id),
+            TokenError::InvalidName => write!(f, "Token name must not be empty"),
+            TokenError::InvalidSymbol { max_len } => write!(
+                f,
+                "Token symbol must be 1-{} ASCII letters or digits",
+                max_len
+            ),
+            TokenError::SymbolTaken(symbol) => {
+                write!(f, "Token symbol {} is already taken", symbol)
+            }
+            TokenError::InsufficientBalance { symbol, account } => {
+                write!(
+                    f,
+                    "Insufficient unlocked {} balance for {}",
+                    symbol, account
+                )
+            }
+            TokenError::InsufficientAllowance {
+                symbol,
+                owner,
+                spender,
+                allowance,
+            } => write!(
+                f,
+                "{} may only spend {} {} from {}",
+                spender, allowance, symbol, owner
+            ),
+            TokenError::BalanceOverflow { symbol, account } => {
+                write!(f, "{} balance overflow for {}", symbol, account)
+            }
+            TokenError::NotTransferable(symbol) => write!(f, "{} is not transferable", symbol),
+            TokenError::NotMintAuthority { symbol, account } => {
+                write!(f, "{} may not mint {}", account, symbol)
+            }
+            TokenError::NotTokenOwner { symbol, owner } => {
+                write!(f, "Only {} can manage {} minters", owner, symbol)
+            }
+            TokenError::NativeMintersGoverned => {
+                write!(f, "Native token mint authorities are set by governance")
+            }
+            TokenError::MaxSupplyExceeded { symbol, amount } => write!(
+                f,
+                "Minting {} {} would exceed the max supply",
+                amount, symbol
+            ),
+            TokenError::MaxSupplyBelowSupply {
+                symbol,
+                max_supply,
+                total_supply,
+            } => write!(
+                f,
+                "Max supply {} is below the current supply of {} {}",
+                max_supply, total_supply, symbol
+            ),
+            TokenError::SupplyUnderflow { symbol, amount } => write!(
+                f,
+                "Burning {} {} would take the total supply below zero",
+                amount, symbol
+            ),
+            TokenError::InvalidVesting(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
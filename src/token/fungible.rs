@@ -1,12 +1,28 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::{error::TokenError, vesting::VestingSchedule};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Token {
     pub name: String,
     pub symbol: String,
     pub total_supply: u64,
+    /// Upper bound on `total_supply`; `None` means uncapped.
+    #[serde(default)]
+    pub max_supply: Option<u64>,
     pub balances: HashMap<String, u64>,
+    /// Owner -> spender -> amount the spender may still move with `transfer_from`.
+    #[serde(default)]
+    pub allowances: HashMap<String, HashMap<String, u64>>,
+    /// Accounts allowed to mint.
+    #[serde(default)]
+    pub mint_authorities: BTreeSet<String>,
+    /// Vesting schedules by beneficiary. Their locked portion counts towards
+    /// the balance but cannot be moved.
+    #[serde(default)]
+    pub vesting: HashMap<String, Vec<VestingSchedule>>,
 }
 
 impl Token {
@@ -18,7 +34,36 @@ impl Token {
             name: name.to_string(),
             symbol: symbol.to_string(),
             total_supply: initial_supply,
+            max_supply: None,
             balances,
+            allowances: HashMap::new(),
+            mint_authorities: BTreeSet::new(),
+            vesting: HashMap::new(),
+        }
+    }
+
+    /// Caps the supply. Fails if more than `max_supply` is already in circulation.
+    pub fn set_max_supply(&mut self, max_supply: Option<u64>) -> Result<(), TokenError> {
+        if let Some(cap) = max_supply.filter(|cap| *cap < self.total_supply) {
+            return Err(TokenError::MaxSupplyBelowSupply {
+                symbol: self.symbol.clone(),
+                max_supply: cap,
+                total_supply: self.total_supply,
+            });
+        }
+        self.max_supply = max_supply;
+        Ok(())
+    }
+
+    pub fn can_mint(&self, account: &str) -> bool {
+        self.mint_authorities.contains(account)
+    }
+
+    pub fn set_mint_authority(&mut self, account: &str, allowed: bool) {
+        if allowed {
+            self.mint_authorities.insert(account.to_string());
+        } else {
+            self.mint_authorities.remove(account);
         }
     }
 
@@ -26,32 +71,156 @@ impl Token {
         *self.balances.get(address).unwrap_or(&0)
     }
 
-    pub fn transfer(&mut self, from: &str, to: &str, amount: u64) -> bool {
+    pub fn locked_at(&self, address: &str, now: DateTime<Utc>) -> u64 {
+        self.vesting
+            .get(address)
+            .into_iter()
+            .flatten()
+            .map(|schedule| schedule.locked_at(now))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Balance minus the portion still locked by vesting schedules.
+    pub fn available_at(&self, address: &str, now: DateTime<Utc>) -> u64 {
+        self.balance_of(address)
+            .saturating_sub(self.locked_at(address, now))
+    }
+
+    /// Moves `schedule.amount` from the grantor to `beneficiary`, locked
+    /// until it vests.
+    pub fn grant_vesting(
+        &mut self,
+        beneficiary: &str,
+        schedule: VestingSchedule,
+    ) -> Result<(), TokenError> {
+        schedule.validate()?;
+        self.transfer(&schedule.grantor, beneficiary, schedule.amount)?;
+        self.vesting
+            .entry(beneficiary.to_string())
+            .or_default()
+            .push(schedule);
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), TokenError> {
+        if self.available_at(from, Utc::now()) < amount {
+            return Err(self.insufficient_balance(from));
+        }
         let from_balance = self.balance_of(from);
-        if from_balance < amount {
-            println!("Insufficient balance for {}", from);
-            return false;
+        let remaining = from_balance
+            .checked_sub(amount)
+            .ok_or_else(|| self.insufficient_balance(from))?;
+
+        self.balances.insert(from.to_string(), remaining);
+        let Some(to_balance) = self.balance_of(to).checked_add(amount) else {
+            self.balances.insert(from.to_string(), from_balance);
+            return Err(self.balance_overflow(to));
+        };
+        self.balances.insert(to.to_string(), to_balance);
+        Ok(())
+    }
+
+    fn insufficient_balance(&self, account: &str) -> TokenError {
+        TokenError::InsufficientBalance {
+            symbol: self.symbol.clone(),
+            account: account.to_string(),
         }
+    }
 
-        self.balances
-            .insert(from.to_string(), from_balance - amount);
-        let to_balance = self.balance_of(to);
-        self.balances.insert(to.to_string(), to_balance + amount);
+    fn balance_overflow(&self, account: &str) -> TokenError {
+        TokenError::BalanceOverflow {
+            symbol: self.symbol.clone(),
+            account: account.to_string(),
+        }
+    }
 
-        println!(
-            "Transferred {} {} from {} to {}",
-            amount, self.symbol, from, to
-        );
-        true
+    pub fn allowance(&self, owner: &str, spender: &str) -> u64 {
+        self.allowances
+            .get(owner)
+            .and_then(|spenders| spenders.get(spender))
+            .copied()
+            .unwrap_or(0)
     }
 
-    pub fn mint(&mut self, to: &str, amount: u64) {
-        let current_balance = self.balance_of(to);
-        self.balances
-            .insert(to.to_string(), current_balance + amount);
-        self.total_supply += amount;
+    /// Sets how much `spender` may move from `owner`, replacing any previous
+    /// allowance. Zero removes it.
+    pub fn approve(&mut self, owner: &str, spender: &str, amount: u64) {
+        let spenders = self.allowances.entry(owner.to_string()).or_default();
+        if amount == 0 {
+            spenders.remove(spender);
+            if spenders.is_empty() {
+                self.allowances.remove(owner);
+            }
+        } else {
+            spenders.insert(spender.to_string(), amount);
+        }
+    }
+
+    /// Moves `amount` from `owner` to `to` on behalf of `spender`, spending
+    /// the allowance. Returns what is left of the allowance.
+    pub fn transfer_from(
+        &mut self,
+        spender: &str,
+        owner: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<u64, TokenError> {
+        let allowance = self.allowance(owner, spender);
+        let Some(remaining) = allowance.checked_sub(amount) else {
+            return Err(TokenError::InsufficientAllowance {
+                symbol: self.symbol.clone(),
+                owner: owner.to_string(),
+                spender: spender.to_string(),
+                allowance,
+            });
+        };
+        self.transfer(owner, to, amount)?;
+        self.approve(owner, spender, remaining);
+        Ok(remaining)
+    }
 
-        println!("Minted {} {} to {}", amount, self.symbol, to);
+    pub fn mint(&mut self, authority: &str, to: &str, amount: u64) -> Result<(), TokenError> {
+        if !self.can_mint(authority) {
+            return Err(TokenError::NotMintAuthority {
+                symbol: self.symbol.clone(),
+                account: authority.to_string(),
+            });
+        }
+        let total_supply = self
+            .total_supply
+            .checked_add(amount)
+            .filter(|supply| self.max_supply.is_none_or(|cap| *supply <= cap))
+            .ok_or_else(|| TokenError::MaxSupplyExceeded {
+                symbol: self.symbol.clone(),
+                amount,
+            })?;
+        let balance = self
+            .balance_of(to)
+            .checked_add(amount)
+            .ok_or_else(|| self.balance_overflow(to))?;
+        self.balances.insert(to.to_string(), balance);
+        self.total_supply = total_supply;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, from: &str, amount: u64) -> Result<(), TokenError> {
+        if self.available_at(from, Utc::now()) < amount {
+            return Err(self.insufficient_balance(from));
+        }
+        let balance = self
+            .balance_of(from)
+            .checked_sub(amount)
+            .ok_or_else(|| self.insufficient_balance(from))?;
+        let total_supply =
+            self.total_supply
+                .checked_sub(amount)
+                .ok_or_else(|| TokenError::SupplyUnderflow {
+                    symbol: self.symbol.clone(),
+                    amount,
+                })?;
+        self.balances.insert(from.to_string(), balance);
+        self.total_supply = total_supply;
+        Ok(())
     }
 }
 
@@ -62,12 +231,62 @@ mod tests {
     #[test]
     fn test_token_transfer() {
         let mut token = Token::new("TestToken", "TT", 1000, "admin");
-        token.mint("user1", 100);
+        token.set_mint_authority("admin", true);
+        token.mint("admin", "user1", 100).unwrap();
         assert_eq!(token.balance_of("user1"), 100);
 
-        let success = token.transfer("user1", "user2", 50);
-        assert!(success);
+        token.transfer("user1", "user2", 50).unwrap();
         assert_eq!(token.balance_of("user1"), 50);
         assert_eq!(token.balance_of("user2"), 50);
     }
+
+    #[test]
+    fn test_token_transfer_from() {
+        let mut token = Token::new("TestToken", "TT", 1000, "admin");
+        token.approve("admin", "escrow", 100);
+        assert!(matches!(
+            token.transfer_from("escrow", "admin", "user1", 101),
+            Err(TokenError::InsufficientAllowance { allowance: 100, .. })
+        ));
+        assert!(token.transfer_from("user1", "admin", "user1", 1).is_err());
+
+        assert_eq!(token.transfer_from("escrow", "admin", "user1", 60), Ok(40));
+        assert_eq!(token.balance_of("user1"), 60);
+        assert_eq!(token.allowance("admin", "escrow"), 40);
+
+        assert_eq!(token.transfer_from("escrow", "admin", "user2", 40), Ok(0));
+        assert!(token.allowances.is_empty());
+    }
+
+    #[test]
+    fn test_token_supply_controls() {
+        let mut token = Token::new("TestToken", "TT", 1000, "admin");
+        assert!(token.mint("admin", "user1", 1).is_err());
+        token.set_mint_authority("minter", true);
+        assert!(token.set_max_supply(Some(999)).is_err());
+        token.set_max_supply(Some(1100)).unwrap();
+
+        token.mint("minter", "user1", 100).unwrap();
+        assert!(token.mint("minter", "user1", 1).is_err());
+        token.set_max_supply(None).unwrap();
+        assert!(token.mint("minter", "user1", u64::MAX).is_err());
+
+        assert!(token.burn("user1", 101).is_err());
+        token.burn("user1", 100).unwrap();
+        assert_eq!(token.total_supply, 1000);
+        assert_eq!(token.balance_of("user1"), 0);
+
+        // Balances out of step with the supply are rejected, not wrapped
+        token.balances.insert("whale".to_string(), u64::MAX);
+        assert!(matches!(
+            token.mint("minter", "whale", 1),
+            Err(TokenError::BalanceOverflow { .. })
+        ));
+        assert!(matches!(
+            token.burn("whale", 1001),
+            Err(TokenError::SupplyUnderflow { amount: 1001, .. })
+        ));
+        assert_eq!(token.balance_of("whale"), u64::MAX);
+        assert_eq!(token.total_supply, 1000);
+    }
 }
@@ -1 +1,5 @@
+pub mod error;
 pub mod fungible;
+pub mod nft;
+pub mod registry;
+pub mod vesting;
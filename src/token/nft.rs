@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::contract::contract::Certificate;
+use crate::events::{EventLog, NFT_CONTRACT};
+
+const MAX_METADATA_URI_LEN: usize = 2048;
+
+/// A unique asset such as a badge or a certificate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Nft {
+    pub id: String,
+    pub owner: String,
+    pub minter: String,
+    pub metadata_uri: String,
+    /// Soulbound NFTs cannot be transferred, only burned by their owner.
+    pub soulbound: bool,
+    /// Certificate the NFT was minted for, if any.
+    pub certificate_id: Option<String>,
+    pub minted_at: DateTime<Utc>,
+}
+
+impl Nft {
+    pub fn minted_log(&self) -> EventLog {
+        let log = EventLog::new(NFT_CONTRACT, "NftMinted")
+            .with("id", &self.id)
+            .with("to", &self.owner)
+            .with("minter", &self.minter)
+            .with("metadata_uri", &self.metadata_uri)
+            .with("soulbound", self.soulbound);
+        match &self.certificate_id {
+            Some(certificate_id) => log.with("certificate", certificate_id),
+            None => log,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NftError {
+    UnknownNft(String),
+    NotOwner { id: String, account: String },
+    Soulbound(String),
+    InvalidMetadataUri { max_len: usize },
+}
+
+impl fmt::Display for NftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NftError::UnknownNft(id) => write!(f, "Unknown NFT: {}", id),
+            NftError::NotOwner { id, account } => {
+                write!(f, "NFT {} is not owned by {}", id, account)
+            }
+            NftError::Soulbound(id) => write!(f, "NFT {} is soulbound", id),
+            NftError::InvalidMetadataUri { max_len } => {
+                write!(f, "metadata_uri must be 1-{} characters", max_len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NftError {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NftRegistry {
+    nfts: BTreeMap<String, Nft>,
+}
+
+impl NftRegistry {
+    pub fn get(&self, id: &str) -> Option<&Nft> {
+        self.nfts.get(id)
+    }
+
+    pub fn owned_by(&self, owner: &str) -> Vec<&Nft> {
+        self.nfts
+            .values()
+            .filter(|nft| nft.owner.eq_ignore_ascii_case(owner))
+            .collect()
+    }
+
+    pub fn mint(
+        &mut self,
+        minter: &str,
+        to: &str,
+        metadata_uri: &str,
+        soulbound: bool,
+    ) -> Result<&Nft, NftError> {
+        if metadata_uri.trim().is_empty() || metadata_uri.len() > MAX_METADATA_URI_LEN {
+            return Err(NftError::InvalidMetadataUri {
+                max_len: MAX_METADATA_URI_LEN,
+            });
+        }
+        Ok(self.insert(Nft {
+            id: uuid::Uuid::new_v4().to_string(),
+            owner: to.to_string(),
+            minter: minter.to_string(),
+            metadata_uri: metadata_uri.trim().to_string(),
+            soulbound,
+            certificate_id: None,
+            minted_at: Utc::now(),
+        }))
+    }
+
+    /// Mints a soulbound NFT to a certificate's holder, pointing at the
+    /// certificate's verifiable credential.
+    pub fn mint_for_certificate(&mut self, certificate: &Certificate) -> &Nft {
+        self.insert(Nft {
+            id: uuid::Uuid::new_v4().to_string(),
+            owner: certificate.issued_to.clone(),
+            minter: certificate.issuer.clone(),
+            metadata_uri: format!("/certificates/{}/credential", certificate.id),
+            soulbound: true,
+            certificate_id: Some(certificate.id.clone()),
+            minted_at: Utc::now(),
+        })
+    }
+
+    fn insert(&mut self, nft: Nft) -> &Nft {
+        self.nfts.entry(nft.id.clone()).or_insert(nft)
+    }
+
+    pub fn transfer(&mut self, id: &str, from: &str, to: &str) -> Result<(), NftError> {
+        let nft = self.owned_mut(id, from)?;
+        if nft.soulbound {
+            return Err(NftError::Soulbound(id.to_string()));
+        }
+        nft.owner = to.to_string();
+        Ok(())
+    }
+
+    pub fn burn(&mut self, id: &str, owner: &str) -> Result<Nft, NftError> {
+        self.owned_mut(id, owner)?;
+        self.nfts
+            .remove(id)
+            .ok_or_else(|| NftError::UnknownNft(id.to_string()))
+    }
+
+    fn owned_mut(&mut self, id: &str, owner: &str) -> Result<&mut Nft, NftError> {
+        let nft = self
+            .nfts
+            .get_mut(id)
+            .ok_or_else(|| NftError::UnknownNft(id.to_string()))?;
+        if !nft.owner.eq_ignore_ascii_case(owner) {
+            return Err(NftError::NotOwner {
+                id: id.to_string(),
+                account: owner.to_string(),
+            });
+        }
+        Ok(nft)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfers_badges_but_not_soulbound_certificates() {
+        let mut nfts = NftRegistry::default();
+        assert!(nfts.mint("0xuni", "alice", " ", false).is_err());
+        let badge = nfts
+            .mint("0xuni", "alice", "ipfs://badge", false)
+            .unwrap()
+            .id
+            .clone();
+        assert!(nfts.transfer(&badge, "bob", "bob").is_err());
+        nfts.transfer(&badge, "alice", "bob").unwrap();
+        assert_eq!(nfts.get(&badge).unwrap().owner, "bob");
+
+        let certificate = Certificate::new("CERT001", "alice", "BSc", "0xuni");
+        let soulbound = nfts.mint_for_certificate(&certificate).id.clone();
+        assert_eq!(nfts.owned_by("ALICE").len(), 1);
+        assert_eq!(
+            nfts.transfer(&soulbound, "alice", "bob"),
+            Err(NftError::Soulbound(soulbound.clone()))
+        );
+        assert!(nfts.burn(&soulbound, "bob").is_err());
+        nfts.burn(&soulbound, "alice").unwrap();
+        assert!(nfts.owned_by("alice").is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{error::TokenError, fungible::Token, vesting::VestingSchedule};
+
+/// Identifier that always resolves to the native token, whatever its symbol.
+pub const NATIVE_TOKEN: &str = "native";
+const MAX_SYMBOL_LEN: usize = 10;
+
+/// A token in the registry with the account that manages it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredToken {
+    #[serde(flatten)]
+    pub token: Token,
+    /// Creator and the only account that can change the token's mint
+    /// authorities; empty for the native token.
+    pub owner: String,
+    /// When false, only mint authorities can send the token, e.g. course
+    /// credits that holders should not trade.
+    pub transferable: bool,
+    pub native: bool,
+}
+
+/// All fungible tokens on the node, keyed by upper-case symbol. The native
+/// token is a reserved entry whose mint authorities are set by governance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRegistry {
+    native: String,
+    tokens: BTreeMap<String, RegisteredToken>,
+}
+
+impl TokenRegistry {
+    pub fn new(native: Token) -> Self {
+        let symbol = native.symbol.to_uppercase();
+        let entry = RegisteredToken {
+            token: native,
+            owner: String::new(),
+            transferable: true,
+            native: true,
+        };
+        TokenRegistry {
+            native: symbol.clone(),
+            tokens: BTreeMap::from([(symbol, entry)]),
+        }
+    }
+
+    fn key(&self, id: &str) -> String {
+        if id.eq_ignore_ascii_case(NATIVE_TOKEN) {
+            self.native.clone()
+        } else {
+            id.to_uppercase()
+        }
+    }
+
+    /// Symbol of the token `id` refers to, e.g. the native symbol for [`NATIVE_TOKEN`].
+    pub fn symbol(&self, id: &str) -> String {
+        self.key(id)
+    }
+
+    pub fn native(&self) -> &Token {
+        &self.tokens[&self.native].token
+    }
+
+    pub fn native_mut(&mut self) -> &mut Token {
+        let key = self.native.clone();
+        &mut self
+            .tokens
+            .get_mut(&key)
+            .expect("native token is registered")
+            .token
+    }
+
+    /// Looks a token up by symbol (case-insensitive) or [`NATIVE_TOKEN`].
+    pub fn get(&self, id: &str) -> Option<&RegisteredToken> {
+        self.tokens.get(&self.key(id))
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut RegisteredToken, TokenError> {
+        let key = self.key(id);
+        self.tokens
+            .get_mut(&key)
+            .ok_or_else(|| TokenError::UnknownToken(id.to_string()))
+    }
+
+    pub fn list(&self) -> Vec<&RegisteredToken> {
+        self.tokens.values().collect()
+    }
+
+    /// Creates a token whose initial supply goes to `owner`, who is also its
+    /// first mint authority.
+    pub fn create(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        initial_supply: u64,
+        max_supply: Option<u64>,
+        owner: &str,
+        transferable: bool,
+    ) -> Result<&RegisteredToken, TokenError> {
+        if name.trim().is_empty() {
+            return Err(TokenError::InvalidName);
+        }
+        if symbol.is_empty()
+            || symbol.len() > MAX_SYMBOL_LEN
+            || !symbol.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(TokenError::InvalidSymbol {
+                max_len: MAX_SYMBOL_LEN,
+            });
+        }
+        let key = symbol.to_uppercase();
+        if key.eq_ignore_ascii_case(NATIVE_TOKEN) || self.tokens.contains_key(&key) {
+            return Err(TokenError::SymbolTaken(key));
+        }
+
+        let mut token = Token::new(name.trim(), &key, initial_supply, owner);
+        token.set_max_supply(max_supply)?;
+        token.set_mint_authority(owner, true);
+        let entry = RegisteredToken {
+            token,
+            owner: owner.to_string(),
+            transferable,
+            native: false,
+        };
+        Ok(self.tokens.entry(key).or_insert(entry))
+    }
+
+    /// Grants or revokes mint permission; only the token owner may do this.
+    pub fn set_minter(
+        &mut self,
+        id: &str,
+        owner: &str,
+        account: &str,
+        allowed: bool,
+    ) -> Result<(), TokenError> {
+        let entry = self.get_mut(id)?;
+        if entry.native {
+            return Err(TokenError::NativeMintersGoverned);
+        }
+        if entry.owner != owner {
+            return Err(TokenError::NotTokenOwner {
+                symbol: entry.token.symbol.clone(),
+                owner: entry.owner.clone(),
+            });
+        }
+        entry.token.set_mint_authority(account, allowed);
+        Ok(())
+    }
+
+    pub fn mint(
+        &mut self,
+        id: &str,
+        minter: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), TokenError> {
+        self.get_mut(id)?.token.mint(minter, to, amount)
+    }
+
+    pub fn burn(&mut self, id: &str, from: &str, amount: u64) -> Result<(), TokenError> {
+        self.get_mut(id)?.token.burn(from, amount)
+    }
+
+    pub fn approve(
+        &mut self,
+        id: &str,
+        owner: &str,
+        spender: &str,
+        amount: u64,
+    ) -> Result<(), TokenError> {
+        self.get_mut(id)?.token.approve(owner, spender, amount);
+        Ok(())
+    }
+
+    /// Spends `spender`'s allowance from `owner` and returns what is left of it.
+    pub fn transfer_from(
+        &mut self,
+        id: &str,
+        spender: &str,
+        owner: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<u64, TokenError> {
+        let entry = self.sendable(id, owner)?;
+        entry.token.transfer_from(spender, owner, to, amount)
+    }
+
+    pub fn grant_vesting(
+        &mut self,
+        id: &str,
+        beneficiary: &str,
+        schedule: VestingSchedule,
+    ) -> Result<(), TokenError> {
+        let entry = self.sendable(id, &schedule.grantor)?;
+        entry.token.grant_vesting(beneficiary, schedule)
+    }
+
+    pub fn transfer(
+        &mut self,
+        id: &str,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), TokenError> {
+        self.sendable(id, from)?.token.transfer(from, to, amount)
+    }
+
+    /// The token, if `sender` may move it.
+    fn sendable(&mut self, id: &str, sender: &str) -> Result<&mut RegisteredToken, TokenError> {
+        let entry = self.get_mut(id)?;
+        if !entry.transferable && !entry.token.can_mint(sender) {
+            return Err(TokenError::NotTransferable(entry.token.symbol.clone()));
+        }
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_tokens_with_per_token_permissions() {
+        let mut registry = TokenRegistry::new(Token::new("Hikma", "HKM", 1_000, "admin"));
+        assert_eq!(registry.get(NATIVE_TOKEN).unwrap().token.symbol, "HKM");
+        assert!(registry
+            .create("Fake", "native", 1, None, "mallory", true)
+            .is_err());
+        assert!(registry
+            .create("Fake", "hkm", 1, None, "mallory", true)
+            .is_err());
+        assert!(registry
+            .create("Course Credit", "cred", 100, Some(99), "0xuni", false)
+            .is_err());
+
+        registry
+            .create("Course Credit", "cred", 100, Some(1_000), "0xuni", false)
+            .unwrap();
+        assert!(registry.mint("CRED", "mallory", "mallory", 5).is_err());
+        registry.mint("cred", "0xuni", "alice", 10).unwrap();
+        assert_eq!(registry.get("CRED").unwrap().token.total_supply, 110);
+
+        // Holders cannot trade a non-transferable token, but its mint authorities can send it
+        assert_eq!(
+            registry.transfer("CRED", "alice", "bob", 5),
+            Err(TokenError::NotTransferable("CRED".to_string()))
+        );
+        registry.transfer("CRED", "0xuni", "bob", 5).unwrap();
+
+        assert!(registry.set_minter("CRED", "alice", "alice", true).is_err());
+        registry.set_minter("CRED", "0xuni", "alice", true).unwrap();
+        registry.transfer("CRED", "alice", "bob", 5).unwrap();
+
+        registry.burn("CRED", "bob", 10).unwrap();
+        assert!(registry.burn("CRED", "bob", 1).is_err());
+        assert_eq!(registry.get("CRED").unwrap().token.total_supply, 100);
+        assert!(registry.mint(NATIVE_TOKEN, "admin", "admin", 1).is_err());
+        assert_eq!(registry.native().balance_of("admin"), 1_000);
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::error::TokenError;
+
+const MAX_VESTING_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
+
+/// Tokens granted to an account that unlock linearly over `duration_seconds`
+/// from `start`, with nothing unlocked before the cliff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    pub grantor: String,
+    pub amount: u64,
+    pub start: DateTime<Utc>,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<(), TokenError> {
+        if self.amount == 0 {
+            return Err(TokenError::InvalidVesting(
+                "Vesting amount must be positive".to_string(),
+            ));
+        }
+        if self.duration_seconds == 0 || self.duration_seconds > MAX_VESTING_SECONDS {
+            return Err(TokenError::InvalidVesting(format!(
+                "Vesting duration must be between 1 and {} seconds",
+                MAX_VESTING_SECONDS
+            )));
+        }
+        if self.cliff_seconds > self.duration_seconds {
+            return Err(TokenError::InvalidVesting(
+                "Vesting cliff cannot be longer than the duration".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn vested_at(&self, now: DateTime<Utc>) -> u64 {
+        let elapsed = (now - self.start).num_seconds();
+        if elapsed < 0 || (elapsed as u64) < self.cliff_seconds {
+            return 0;
+        }
+        let elapsed = elapsed as u64;
+        if elapsed >= self.duration_seconds {
+            return self.amount;
+        }
+        // Cannot overflow or exceed `amount`: elapsed < duration
+        (self.amount as u128 * elapsed as u128 / self.duration_seconds as u128) as u64
+    }
+
+    pub fn locked_at(&self, now: DateTime<Utc>) -> u64 {
+        self.amount - self.vested_at(now)
+    }
+
+    pub fn end(&self) -> DateTime<Utc> {
+        self.start
+            .checked_add_signed(Duration::seconds(self.duration_seconds as i64))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::fungible::Token;
+
+    #[test]
+    fn unlocks_after_cliff_then_linearly() {
+        let start = Utc::now();
+        let schedule = VestingSchedule {
+            grantor: "admin".to_string(),
+            amount: 1_000,
+            start,
+            cliff_seconds: 100,
+            duration_seconds: 400,
+        };
+        let at = |seconds| start + Duration::seconds(seconds);
+        assert_eq!(schedule.vested_at(at(-10)), 0);
+        assert_eq!(schedule.vested_at(at(99)), 0);
+        assert_eq!(schedule.vested_at(at(100)), 250);
+        assert_eq!(schedule.vested_at(at(200)), 500);
+        assert_eq!(schedule.vested_at(at(400)), 1_000);
+        assert_eq!(schedule.end(), at(400));
+
+        let mut token = Token::new("TestToken", "TT", 2_000, "admin");
+        token.grant_vesting("alice", schedule).unwrap();
+        assert_eq!(token.balance_of("alice"), 1_000);
+        assert_eq!(token.locked_at("alice", at(200)), 500);
+        assert!(token.transfer("alice", "bob", 1).is_err());
+        assert!(token.burn("alice", 1).is_err());
+        token.transfer("admin", "alice", 10).unwrap();
+        token.transfer("alice", "bob", 10).unwrap();
+    }
+}